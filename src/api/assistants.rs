@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{encode_path_segment, FunctionInfo, IntoRequest, ListOrder, ListResponse, RequestContext, Tool};
+
+/// The `OpenAI-Beta` header value of the Assistants API.
+pub(crate) const ASSISTANTS_BETA: &str = "assistants=v2";
+
+/// Creates an assistant with a model and instructions.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateAssistantRequest {
+    /// ID of the model to use.
+    pub model: String,
+    /// The name of the assistant. The maximum length is 256 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The description of the assistant. The maximum length is 512 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The system instructions that the assistant uses. The maximum length is 256,000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// A list of tool enabled on the assistant. There can be a maximum of 128 tools per assistant.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<AssistantTool>,
+    /// Resources used by the assistant's tools, e.g. files for code_interpreter and vector stores for file_search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_resources: Option<ToolResources>,
+    /// Set of 16 key-value pairs that can be attached to the assistant.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    /// What sampling temperature to use, between 0 and 2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// An alternative to sampling with temperature, called nucleus sampling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+}
+
+impl CreateAssistantRequest {
+    pub fn new(model: impl Into<String>) -> Self {
+        CreateAssistantRequest {
+            model: model.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for CreateAssistantRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("assistants").json(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+/// Modifies an assistant. Only the fields that are set are changed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModifyAssistantRequest {
+    #[serde(skip)]
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Replaces the tools of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<AssistantTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_resources: Option<ToolResources>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+}
+
+impl ModifyAssistantRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        ModifyAssistantRequest {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for ModifyAssistantRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("assistants/{}", encode_path_segment(&self.id)))
+            .json(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetrieveAssistantRequest {
+    pub id: String,
+}
+
+impl IntoRequest for RetrieveAssistantRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("assistants/{}", encode_path_segment(&self.id)))
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteAssistantRequest {
+    pub id: String,
+}
+
+impl IntoRequest for DeleteAssistantRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.delete(&format!("assistants/{}", encode_path_segment(&self.id)))
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+/// Returns a list of assistants.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListAssistantsRequest {
+    /// A cursor for use in pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// A cursor for use in pagination: the ID of the first object of the next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// A limit on the number of objects to be returned, between 1 and 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<ListOrder>,
+}
+
+impl IntoRequest for ListAssistantsRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get("assistants").query(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+/// A tool of an assistant or a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AssistantTool {
+    CodeInterpreter,
+    FileSearch {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        file_search: Option<FileSearchOptions>,
+    },
+    Function {
+        function: FunctionInfo,
+    },
+}
+
+impl From<Tool> for AssistantTool {
+    fn from(tool: Tool) -> Self {
+        AssistantTool::Function {
+            function: tool.function,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileSearchOptions {
+    /// The maximum number of results the file search tool should output, between 1 and 50.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_num_results: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolResources {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_interpreter: Option<CodeInterpreterResources>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_search: Option<FileSearchResources>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeInterpreterResources {
+    /// The IDs of the files made available to the code_interpreter tool. At most 20.
+    #[serde(default)]
+    pub file_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileSearchResources {
+    /// The IDs of the vector stores searched by the file_search tool. At most 1.
+    #[serde(default)]
+    pub vector_store_ids: Vec<String>,
+}
+
+/// Represents an assistant that can call the model and use tools.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Assistant {
+    pub id: String,
+    /// The object type, which is always "assistant".
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the assistant was created.
+    pub created_at: u64,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// ID of the model the assistant uses.
+    pub model: String,
+    #[serde(default)]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub tools: Vec<AssistantTool>,
+    #[serde(default)]
+    pub tool_resources: Option<ToolResources>,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+}
+
+pub type ListAssistantsResponse = ListResponse<Assistant>;
+
+/// The answer of the delete endpoints of the Assistants API.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DeletionStatus {
+    pub id: String,
+    /// The type of the deleted object, e.g. "assistant.deleted".
+    pub object: String,
+    pub deleted: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn assistant() -> serde_json::Value {
+        json!({
+            "id": "asst_abc123",
+            "object": "assistant",
+            "created_at": 1698984975,
+            "name": "Math Tutor",
+            "description": null,
+            "model": "gpt-4o",
+            "instructions": "You are a personal math tutor.",
+            "tools": [{ "type": "code_interpreter" }, { "type": "file_search", "file_search": { "max_num_results": 5 } }],
+            "tool_resources": { "file_search": { "vector_store_ids": ["vs_1"] } },
+            "metadata": {},
+            "top_p": 1.0,
+            "temperature": 1.0,
+            "response_format": "auto"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_assistants_crud() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::json(assistant()),
+            MockResponse::json(assistant()),
+            MockResponse::json(json!({ "object": "list", "data": [assistant()], "first_id": "asst_abc123", "last_id": "asst_abc123", "has_more": false })),
+            MockResponse::json(json!({ "id": "asst_abc123", "object": "assistant.deleted", "deleted": true })),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = CreateAssistantRequest {
+            name: Some("Math Tutor".into()),
+            instructions: Some("You are a personal math tutor.".into()),
+            tools: vec![
+                AssistantTool::CodeInterpreter,
+                Tool::function("add", None, json!({ "type": "object" })).into(),
+            ],
+            ..CreateAssistantRequest::new("gpt-4o")
+        };
+        let assistant = sdk.create_assistant(req).await?;
+        assert_eq!(assistant.tools.len(), 2);
+        assert_eq!(
+            assistant.tool_resources.unwrap().file_search.unwrap().vector_store_ids,
+            ["vs_1"]
+        );
+        let req = ModifyAssistantRequest {
+            instructions: Some("Be brief.".into()),
+            ..ModifyAssistantRequest::new("asst_abc123")
+        };
+        sdk.modify_assistant(req).await?;
+        assert_eq!(sdk.list_assistants(Default::default()).await?.data.len(), 1);
+        assert!(sdk.delete_assistant("asst_abc123").await?.deleted);
+
+        let requests = server.requests();
+        assert!(requests.iter().all(|req| req.header("openai-beta") == Some("assistants=v2")));
+        assert_eq!(
+            server.calls(),
+            [
+                "POST /assistants",
+                "POST /assistants/asst_abc123",
+                "GET /assistants",
+                "DELETE /assistants/asst_abc123",
+            ]
+        );
+        assert_eq!(
+            requests[0].json()["tools"],
+            json!([
+                { "type": "code_interpreter" },
+                { "type": "function", "function": { "name": "add", "parameters": { "type": "object" } } }
+            ])
+        );
+        assert_eq!(requests[1].json(), json!({ "instructions": "Be brief." }));
+        Ok(())
+    }
+}
@@ -0,0 +1,628 @@
+use std::collections::{HashMap, HashSet};
+
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    encode_path_segment, ChatCompletionRequest, ChatCompletionResponse, CreateEmbeddingRequest,
+    CreateEmbeddingResponse, CreateModerationRequest, CreateModerationResponse, CreateResponseRequest, IntoRequest,
+    ListResponse, LlmError, RequestContext, Response, Result,
+};
+
+/// A request type that can be run through the Batch API, with the response it produces.
+pub trait BatchRequest: IntoRequest {
+    type Response: DeserializeOwned + std::fmt::Debug;
+}
+
+impl BatchRequest for ChatCompletionRequest {
+    type Response = ChatCompletionResponse;
+}
+
+impl BatchRequest for CreateEmbeddingRequest {
+    type Response = CreateEmbeddingResponse;
+}
+
+impl BatchRequest for CreateModerationRequest {
+    type Response = CreateModerationResponse;
+}
+
+impl BatchRequest for CreateResponseRequest {
+    type Response = Response;
+}
+
+/// The requests of a batch, each with a `custom_id` that is unique within the batch. All of
+/// them must go to the same endpoint.
+#[derive(Debug, Clone)]
+pub struct BatchInput<R> {
+    requests: Vec<(String, R)>,
+}
+
+impl<R> Default for BatchInput<R> {
+    fn default() -> Self {
+        BatchInput { requests: vec![] }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct BatchInputLine<'a> {
+    custom_id: &'a str,
+    method: &'a str,
+    url: &'a str,
+    body: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchOutputLine {
+    custom_id: String,
+    #[serde(default)]
+    response: Option<BatchOutputResponse>,
+    #[serde(default)]
+    error: Option<BatchRequestError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchOutputResponse {
+    status_code: u16,
+    #[serde(default)]
+    body: Value,
+}
+
+impl<R: BatchRequest + Clone> BatchInput<R> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, custom_id: impl Into<String>, req: R) -> &mut Self {
+        self.requests.push((custom_id.into(), req));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Encodes the requests as the JSONL input file of a batch, and returns the endpoint they go to.
+    pub fn to_jsonl(&self) -> Result<(BatchEndpoint, Vec<u8>)> {
+        let invalid = |msg: String| Err(LlmError::Validation(msg));
+        let ctx = RequestContext::new("https://api.openai.com/v1", Client::new());
+        let mut seen = HashSet::new();
+        let mut endpoint = None;
+        let mut out = vec![];
+        for (custom_id, req) in &self.requests {
+            if !seen.insert(custom_id.as_str()) {
+                return invalid(format!("duplicate custom_id: {custom_id}"));
+            }
+            let req = req.clone().into_request(&ctx).build()?;
+            let url = req.url().path();
+            match &endpoint {
+                None => endpoint = Some(BatchEndpoint::from_path(url)),
+                Some(endpoint) if endpoint.path() != url => {
+                    return invalid(format!("all requests of a batch must go to {}, got {url}", endpoint.path()))
+                }
+                _ => {}
+            }
+            let body = req.body().and_then(|body| body.as_bytes()).unwrap_or_default();
+            let line = BatchInputLine {
+                custom_id,
+                method: req.method().as_str(),
+                url,
+                body: serde_json::from_slice(body).map_err(|source| LlmError::Deserialize {
+                    source,
+                    body: String::from_utf8_lossy(body).into_owned(),
+                })?,
+            };
+            serde_json::to_writer(&mut out, &line).expect("a json value always serializes");
+            out.push(b'\n');
+        }
+        match endpoint {
+            Some(endpoint) => Ok((endpoint, out)),
+            None => invalid("a batch needs at least one request".into()),
+        }
+    }
+
+    /// Joins the lines of the output and error files back to the requests by `custom_id`,
+    /// keeping the input order.
+    pub fn join(self, output: &[u8], errors: &[u8]) -> Result<Vec<BatchResult<R>>> {
+        let mut lines = HashMap::new();
+        for line in output.split(|&b| b == b'\n').chain(errors.split(|&b| b == b'\n')) {
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            let line: BatchOutputLine = serde_json::from_slice(line).map_err(|source| LlmError::Deserialize {
+                source,
+                body: String::from_utf8_lossy(line).into_owned(),
+            })?;
+            lines.insert(line.custom_id.clone(), line);
+        }
+        Ok(self
+            .requests
+            .into_iter()
+            .map(|(custom_id, request)| {
+                let response = lines.remove(&custom_id).map(BatchOutputLine::into_result);
+                BatchResult {
+                    custom_id,
+                    request,
+                    response,
+                }
+            })
+            .collect())
+    }
+}
+
+impl BatchOutputLine {
+    fn into_result<T: DeserializeOwned>(self) -> Result<T> {
+        match (self.response, self.error) {
+            (Some(res), _) if (200..300).contains(&res.status_code) => {
+                serde_json::from_value(res.body.clone()).map_err(|source| LlmError::Deserialize {
+                    source,
+                    body: res.body.to_string(),
+                })
+            }
+            (Some(res), _) => {
+                let status = StatusCode::from_u16(res.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                Err(LlmError::from_response(status, res.body.to_string()))
+            }
+            (None, error) => Err(error.unwrap_or_default().to_error(StatusCode::INTERNAL_SERVER_ERROR)),
+        }
+    }
+}
+
+/// The outcome of one request of a batch.
+#[derive(Debug)]
+pub struct BatchResult<R: BatchRequest> {
+    pub custom_id: String,
+    pub request: R,
+    /// The response, or the error the request failed with. When the batch failed validation or
+    /// expired, the requests that never ran get the batch's error. `None` when the batch was
+    /// cancelled before the request was run.
+    pub response: Option<Result<R::Response>>,
+}
+
+/// A finished batch with the outcome of every request, in input order.
+#[derive(Debug)]
+pub struct BatchRun<R: BatchRequest> {
+    pub batch: Batch,
+    pub results: Vec<BatchResult<R>>,
+}
+
+impl<R: BatchRequest> BatchRun<R> {
+    /// Gives the requests without an outcome the error of a failed or expired batch.
+    pub(crate) fn new(batch: Batch, mut results: Vec<BatchResult<R>>) -> Self {
+        for (index, result) in results.iter_mut().enumerate() {
+            if result.response.is_none() {
+                result.response = batch.unrun_error(index as u64 + 1).map(Err);
+            }
+        }
+        BatchRun { batch, results }
+    }
+}
+
+/// The endpoint all requests of a batch go to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatchEndpoint {
+    #[serde(rename = "/v1/chat/completions")]
+    ChatCompletions,
+    #[serde(rename = "/v1/embeddings")]
+    Embeddings,
+    #[serde(rename = "/v1/completions")]
+    Completions,
+    #[serde(rename = "/v1/responses")]
+    Responses,
+    #[serde(rename = "/v1/moderations")]
+    Moderations,
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl BatchEndpoint {
+    pub fn path(&self) -> &str {
+        match self {
+            BatchEndpoint::ChatCompletions => "/v1/chat/completions",
+            BatchEndpoint::Embeddings => "/v1/embeddings",
+            BatchEndpoint::Completions => "/v1/completions",
+            BatchEndpoint::Responses => "/v1/responses",
+            BatchEndpoint::Moderations => "/v1/moderations",
+            BatchEndpoint::Other(path) => path,
+        }
+    }
+
+    fn from_path(path: &str) -> Self {
+        serde_json::from_value(Value::String(path.into())).expect("an untagged fallback always matches")
+    }
+}
+
+/// Creates and executes a batch from an uploaded file of requests.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateBatchRequest {
+    /// The ID of an uploaded file that contains requests for the new batch, uploaded with the batch purpose.
+    pub input_file_id: String,
+    /// The endpoint to be used for all requests in the batch.
+    pub endpoint: BatchEndpoint,
+    /// The time frame within which the batch should be processed. Currently only 24h is supported.
+    pub completion_window: String,
+    /// Set of 16 key-value pairs that can be attached to the batch.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+impl CreateBatchRequest {
+    pub fn new(input_file_id: impl Into<String>, endpoint: BatchEndpoint) -> Self {
+        CreateBatchRequest {
+            input_file_id: input_file_id.into(),
+            endpoint,
+            completion_window: "24h".into(),
+            metadata: HashMap::new(),
+        }
+    }
+}
+
+impl IntoRequest for CreateBatchRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("batches").json(&self)
+    }
+}
+
+/// Retrieves a batch.
+#[derive(Debug, Clone)]
+pub struct RetrieveBatchRequest {
+    pub id: String,
+}
+
+impl IntoRequest for RetrieveBatchRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("batches/{}", encode_path_segment(&self.id)))
+    }
+}
+
+/// Cancels an in-progress batch. The batch will be in status cancelling for up to 10 minutes.
+#[derive(Debug, Clone)]
+pub struct CancelBatchRequest {
+    pub id: String,
+}
+
+impl IntoRequest for CancelBatchRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("batches/{}/cancel", encode_path_segment(&self.id)))
+    }
+}
+
+/// Lists your organization's batches.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListBatchesRequest {
+    /// A cursor for use in pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// A limit on the number of objects to be returned, between 1 and 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl IntoRequest for ListBatchesRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get("batches").query(&self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Batch {
+    pub id: String,
+    /// The object type, which is always "batch".
+    pub object: String,
+    /// The OpenAI API endpoint used by the batch.
+    pub endpoint: BatchEndpoint,
+    /// Validation errors of the input file.
+    #[serde(default)]
+    pub errors: Option<BatchErrors>,
+    /// The ID of the input file for the batch.
+    pub input_file_id: String,
+    /// The time frame within which the batch should be processed.
+    pub completion_window: String,
+    pub status: BatchStatus,
+    /// The ID of the file containing the outputs of successfully executed requests.
+    #[serde(default)]
+    pub output_file_id: Option<String>,
+    /// The ID of the file containing the outputs of requests with errors.
+    #[serde(default)]
+    pub error_file_id: Option<String>,
+    /// The Unix timestamp (in seconds) for when the batch was created.
+    pub created_at: u64,
+    #[serde(default)]
+    pub in_progress_at: Option<u64>,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub finalizing_at: Option<u64>,
+    #[serde(default)]
+    pub completed_at: Option<u64>,
+    #[serde(default)]
+    pub failed_at: Option<u64>,
+    #[serde(default)]
+    pub expired_at: Option<u64>,
+    #[serde(default)]
+    pub cancelling_at: Option<u64>,
+    #[serde(default)]
+    pub cancelled_at: Option<u64>,
+    /// The request counts for different statuses within the batch.
+    #[serde(default)]
+    pub request_counts: Option<BatchRequestCounts>,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    Validating,
+    Failed,
+    InProgress,
+    Finalizing,
+    Completed,
+    Expired,
+    Cancelling,
+    Cancelled,
+}
+
+impl Batch {
+    /// Why the request on `line` (1-based) of the input file never ran, for a batch that failed
+    /// validation or expired.
+    fn unrun_error(&self, line: u64) -> Option<LlmError> {
+        match self.status {
+            BatchStatus::Failed => {
+                let errors = self.errors.as_ref().map(|errors| errors.data.as_slice()).unwrap_or_default();
+                let error = errors
+                    .iter()
+                    .find(|error| error.line == Some(line))
+                    .or_else(|| errors.iter().find(|error| error.line.is_none()))
+                    .cloned()
+                    .unwrap_or_else(|| BatchRequestError {
+                        message: "the batch failed validation".into(),
+                        ..Default::default()
+                    });
+                Some(error.to_error(StatusCode::BAD_REQUEST))
+            }
+            BatchStatus::Expired => Some(
+                BatchRequestError {
+                    code: Some("batch_expired".into()),
+                    message: "the request was not run before the batch expired".into(),
+                    ..Default::default()
+                }
+                .to_error(StatusCode::REQUEST_TIMEOUT),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl BatchStatus {
+    /// Whether the batch has stopped and its status will not change anymore.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            BatchStatus::Failed | BatchStatus::Completed | BatchStatus::Expired | BatchStatus::Cancelled
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BatchErrors {
+    pub object: String,
+    pub data: Vec<BatchRequestError>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct BatchRequestError {
+    /// An error code identifying the error type.
+    #[serde(default)]
+    pub code: Option<String>,
+    /// A human-readable message providing more details about the error.
+    #[serde(default)]
+    pub message: String,
+    /// The name of the parameter that caused the error, if applicable.
+    #[serde(default)]
+    pub param: Option<String>,
+    /// The line number of the input file where the error occurred, if applicable.
+    #[serde(default)]
+    pub line: Option<u64>,
+}
+
+impl BatchRequestError {
+    fn to_error(&self, status: StatusCode) -> LlmError {
+        let body = serde_json::json!({ "error": { "message": self.message, "code": self.code, "param": self.param } });
+        LlmError::from_response(status, body.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct BatchRequestCounts {
+    /// Total number of requests in the batch.
+    pub total: u64,
+    /// Number of requests that have been completed successfully.
+    pub completed: u64,
+    /// Number of requests that have failed.
+    pub failed: u64,
+}
+
+pub type ListBatchesResponse = ListResponse<Batch>;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        mock::{MockResponse, MockServer},
+        ChatCompletionMessage, ChatCompletionModel, PollOptions, LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn chat(question: &str) -> ChatCompletionRequest {
+        ChatCompletionRequest::new(ChatCompletionModel::Gpt4oMini, vec![ChatCompletionMessage::user(question)])
+    }
+
+    fn batch(status: &str) -> serde_json::Value {
+        json!({
+            "id": "batch_abc123",
+            "object": "batch",
+            "endpoint": "/v1/chat/completions",
+            "errors": null,
+            "input_file_id": "file-in",
+            "completion_window": "24h",
+            "status": status,
+            "output_file_id": if status == "completed" { json!("file-out") } else { json!(null) },
+            "error_file_id": if status == "completed" { json!("file-err") } else { json!(null) },
+            "created_at": 1711471533,
+            "request_counts": { "total": 3, "completed": 1, "failed": 1 }
+        })
+    }
+
+    fn output_line(custom_id: &str, content: &str) -> String {
+        json!({
+            "id": "batch_req_1",
+            "custom_id": custom_id,
+            "response": {
+                "status_code": 200,
+                "request_id": "req_1",
+                "body": {
+                    "id": "chatcmpl-1",
+                    "object": "chat.completion",
+                    "created": 1,
+                    "model": "gpt-4o-mini",
+                    "choices": [{ "index": 0, "message": { "role": "assistant", "content": content }, "finish_reason": "stop" }]
+                }
+            },
+            "error": null
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_batch_input_to_jsonl() -> Result<()> {
+        let mut input = BatchInput::new();
+        input.push("q1", chat("Hello")).push("q2", chat("World"));
+        let (endpoint, jsonl) = input.to_jsonl()?;
+        assert_eq!(endpoint, BatchEndpoint::ChatCompletions);
+        let lines: Vec<serde_json::Value> = String::from_utf8(jsonl)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            json!({
+                "custom_id": "q2",
+                "method": "POST",
+                "url": "/v1/chat/completions",
+                "body": { "model": "gpt-4o-mini", "messages": [{ "role": "user", "content": "World" }] }
+            })
+        );
+
+        input.push("q1", chat("again"));
+        assert!(matches!(input.to_jsonl(), Err(LlmError::Validation(_))));
+        assert!(matches!(BatchInput::<ChatCompletionRequest>::new().to_jsonl(), Err(LlmError::Validation(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_run_batch() -> Result<()> {
+        let output = format!("{}\n", output_line("q3", "third"));
+        let errors = [
+            json!({ "id": "batch_req_2", "custom_id": "q1", "response": { "status_code": 400, "request_id": "req_2", "body": { "error": { "message": "Invalid model", "type": "invalid_request_error", "code": "model_not_found" } } }, "error": null }),
+        ]
+        .map(|line| line.to_string())
+        .join("\n");
+        let server = MockServer::start(vec![
+            MockResponse::json(json!({ "id": "file-in", "object": "file", "bytes": 300, "created_at": 1, "filename": "batch.jsonl", "purpose": "batch" })),
+            MockResponse::json(batch("validating")),
+            MockResponse::json(batch("in_progress")),
+            MockResponse::json(batch("completed")),
+            MockResponse::new(200, output),
+            MockResponse::new(200, errors),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let mut input = BatchInput::new();
+        input.push("q1", chat("one")).push("q2", chat("two")).push("q3", chat("three"));
+        let poll = PollOptions::default().with_interval(Duration::from_millis(1), Duration::from_millis(5));
+        let run = sdk.run_batch(input, poll).await?;
+        assert_eq!(run.batch.status, BatchStatus::Completed);
+
+        let ids: Vec<_> = run.results.iter().map(|r| r.custom_id.as_str()).collect();
+        assert_eq!(ids, ["q1", "q2", "q3"]);
+        let err = run.results[0].response.as_ref().unwrap().as_ref().unwrap_err();
+        assert!(err.is_bad_request());
+        assert_eq!(err.api_error().unwrap().code.as_deref(), Some("model_not_found"));
+        assert!(run.results[1].response.is_none());
+        let res = run.results[2].response.as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(res.choices[0].message.content.as_deref(), Some("third"));
+
+        let requests = server.requests();
+        assert_eq!(
+            server.calls(),
+            [
+                "POST /files",
+                "POST /batches",
+                "GET /batches/batch_abc123",
+                "GET /batches/batch_abc123",
+                "GET /files/file-out/content",
+                "GET /files/file-err/content",
+            ]
+        );
+        let upload = String::from_utf8_lossy(&requests[0].body);
+        assert!(upload.contains("name=\"purpose\"\r\n\r\nbatch\r\n"));
+        assert!(upload.contains("{\"custom_id\":\"q2\",\"method\":\"POST\",\"url\":\"/v1/chat/completions\""));
+        assert_eq!(
+            requests[1].json(),
+            json!({ "input_file_id": "file-in", "endpoint": "/v1/chat/completions", "completion_window": "24h" })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_run_batch_failed_validation() -> Result<()> {
+        let mut failed = batch("failed");
+        failed["errors"] = json!({ "object": "list", "data": [
+            { "code": "invalid_value", "message": "Model 'gpt-5o' not supported.", "param": "body.model", "line": 2 },
+            { "code": "too_many_requests", "message": "The batch has too many requests.", "param": null, "line": null },
+        ] });
+        let server = MockServer::start(vec![
+            MockResponse::json(json!({ "id": "file-in", "object": "file", "bytes": 300, "created_at": 1, "filename": "batch.jsonl", "purpose": "batch" })),
+            MockResponse::json(batch("validating")),
+            MockResponse::json(failed),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let mut input = BatchInput::new();
+        input.push("q1", chat("one")).push("q2", chat("two"));
+        let poll = PollOptions::default().with_interval(Duration::from_millis(1), Duration::from_millis(5));
+        let run = sdk.run_batch(input, poll).await?;
+        assert_eq!(run.batch.status, BatchStatus::Failed);
+        let errors: Vec<_> = run
+            .results
+            .iter()
+            .map(|r| r.response.as_ref().unwrap().as_ref().unwrap_err())
+            .collect();
+        assert!(errors.iter().all(|err| err.is_bad_request()));
+        assert_eq!(errors[0].api_error().unwrap().code.as_deref(), Some("too_many_requests"));
+        assert_eq!(errors[1].api_error().unwrap().param.as_deref(), Some("body.model"));
+        assert_eq!(server.requests().len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_create_response_batch_jsonl() -> Result<()> {
+        let mut input = BatchInput::new();
+        input.push("r1", CreateResponseRequest::new("gpt-4o-mini", "Hello"));
+        let (endpoint, jsonl) = input.to_jsonl()?;
+        assert_eq!(endpoint, BatchEndpoint::Responses);
+        let line: serde_json::Value = serde_json::from_slice(&jsonl)?;
+        assert_eq!(line["body"], json!({ "model": "gpt-4o-mini", "input": "Hello" }));
+        Ok(())
+    }
+}
@@ -1,5 +1,700 @@
-struct ChatCompletionRequest {
+use std::collections::{BTreeMap, HashMap};
+
+use futures_util::{stream::BoxStream, Stream, StreamExt};
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use reqwest::RequestBuilder;
+
+use crate::{IntoRequest, LlmError, RequestContext, Result, ToolFunction};
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ChatCompletionRequest {
+    /// A list of messages comprising the conversation so far.
+    pub messages: Vec<ChatCompletionMessage>,
+    /// ID of the model to use.
+    pub model: ChatCompletionModel,
+    /// Number between -2.0 and 2.0. Positive values penalize new tokens based on their existing frequency in the text so far, decreasing the model's likelihood to repeat the same line verbatim.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    /// Modify the likelihood of specified tokens appearing in the completion. Maps token ids to a bias value from -100 to 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<HashMap<u32, f32>>,
+    /// The maximum number of tokens to generate in the chat completion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+    /// How many chat completion choices to generate for each input message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<usize>,
+    /// Whether to enable parallel function calling during tool use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    /// Number between -2.0 and 2.0. Positive values penalize new tokens based on whether they appear in the text so far, increasing the model's likelihood to talk about new topics.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    /// If specified, the system will make a best effort to sample deterministically, such that repeated requests with the same seed and parameters should return the same result.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    /// Up to 4 sequences where the API will stop generating further tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Vec<String>>,
+    /// If set, partial message deltas will be sent as server-sent events. Set by `LLMSDK::chat_completion_stream`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    /// Options for streaming response. Only set this when you set stream: true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<ChatCompletionStreamOptions>,
+    /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Controls which (if any) tool is called by the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    /// A list of tools the model may call. Currently, only functions are supported as a tool.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<Tool>,
+    /// An alternative to sampling with temperature, called nucleus sampling, where the model considers the results of the tokens with top_p probability mass. We generally recommend altering this or temperature but not both.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+impl ChatCompletionRequest {
+    pub fn new(model: ChatCompletionModel, messages: impl Into<Vec<ChatCompletionMessage>>) -> Self {
+        ChatCompletionRequest {
+            messages: messages.into(),
+            model,
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for ChatCompletionRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("chat/completions")
+           .json(&self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tool {
+    /// The type of the tool. Currently, only function is supported.
+    pub r#type: ToolType,
+    /// The function the model may call.
+    pub function: FunctionInfo,
+}
+
+impl Tool {
+    pub fn function(name: impl Into<String>, description: Option<String>, parameters: Value) -> Self {
+        Tool {
+            r#type: ToolType::Function,
+            function: FunctionInfo {
+                name: name.into(),
+                description,
+                parameters,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolType {
+    #[default]
+    Function,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionInfo {
+    /// The name of the function to be called. Must be a-z, A-Z, 0-9, or contain underscores and dashes, with a maximum length of 64.
+    pub name: String,
+    /// A description of what the function does, used by the model to choose when and how to call the function.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The parameters the functions accepts, described as a JSON Schema object.
+    pub parameters: Value,
+}
+
+/// Controls which (if any) tool is called by the model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ToolChoice {
+    /// The model will not call any tool and instead generates a message.
+    None,
+    /// The model can pick between generating a message or calling one or more tools.
+    #[default]
+    Auto,
+    /// The model must call one or more tools.
+    Required,
+    /// Forces the model to call the named function.
+    Function(String),
+}
+
+impl Serialize for ToolChoice {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ToolChoice::None => serializer.serialize_str("none"),
+            ToolChoice::Auto => serializer.serialize_str("auto"),
+            ToolChoice::Required => serializer.serialize_str("required"),
+            ToolChoice::Function(name) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("type", &ToolType::Function)?;
+                map.serialize_entry("function", &serde_json::json!({ "name": name }))?;
+                map.end()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ChatCompletionStreamOptions {
+    /// If set, an additional chunk will be streamed before the data: [DONE] message, carrying the token usage for the entire request.
+    pub include_usage: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub enum ChatCompletionModel {
+    #[default]
+    #[serde(rename = "gpt-4o-mini")]
+    Gpt4oMini,
+    #[serde(rename = "gpt-4o")]
+    Gpt4o,
+    #[serde(rename = "gpt-4-turbo")]
+    Gpt4Turbo,
+    #[serde(rename = "gpt-4")]
+    Gpt4,
+    #[serde(rename = "gpt-3.5-turbo")]
+    Gpt35Turbo,
+    /// Any other model id, e.g. a fine-tuned model.
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "role", rename_all = "snake_case")]
+pub enum ChatCompletionMessage {
+    /// A message that sets the behavior of the assistant.
+    System(SystemMessage),
+    /// A message written by the end user.
+    User(UserMessage),
+    /// A message previously generated by the model.
+    Assistant(AssistantMessage),
+    /// The result of a tool call, sent back to the model.
+    Tool(ToolMessage),
+}
+
+impl ChatCompletionMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        ChatCompletionMessage::System(SystemMessage {
+            content: content.into(),
+            name: None,
+        })
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        ChatCompletionMessage::User(UserMessage {
+            content: content.into(),
+            name: None,
+        })
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        ChatCompletionMessage::Assistant(AssistantMessage {
+            content: Some(content.into()),
+            ..Default::default()
+        })
+    }
+
+    pub fn tool(tool_call_id: impl Into<String>, content: impl Into<String>) -> Self {
+        ChatCompletionMessage::Tool(ToolMessage {
+            content: content.into(),
+            tool_call_id: tool_call_id.into(),
+        })
+    }
+}
+
+impl From<AssistantMessage> for ChatCompletionMessage {
+    fn from(message: AssistantMessage) -> Self {
+        ChatCompletionMessage::Assistant(message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemMessage {
+    /// The contents of the system message.
+    pub content: String,
+    /// An optional name for the participant. Provides the model information to differentiate between participants of the same role.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserMessage {
+    /// The contents of the user message.
+    pub content: String,
+    /// An optional name for the participant. Provides the model information to differentiate between participants of the same role.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
 }
 
-struct ChatCompletionResponse {
-}
\ No newline at end of file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssistantMessage {
+    /// The contents of the assistant message.
+    #[serde(default)]
+    pub content: Option<String>,
+    /// An optional name for the participant. Provides the model information to differentiate between participants of the same role.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The tool calls generated by the model, such as function calls.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCall>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolCall {
+    /// The ID of the tool call. Send it back as `tool_call_id` of the tool message.
+    pub id: String,
+    /// The type of the tool. Currently, only function is supported.
+    pub r#type: ToolType,
+    /// The function that the model called.
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionCall {
+    /// The name of the function to call.
+    pub name: String,
+    /// The arguments to call the function with, as generated by the model in JSON format. The model does not always generate valid JSON, so validate them before calling your function.
+    pub arguments: String,
+}
+
+impl FunctionCall {
+    /// Whether this call targets the function `T`.
+    pub fn is<T: ToolFunction>(&self) -> bool {
+        self.name == T::name()
+    }
+
+    /// Parses the arguments into the parameters of `T`.
+    pub fn parse<T: ToolFunction>(&self) -> Result<T> {
+        T::parse_arguments(&self.arguments)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolMessage {
+    /// The contents of the tool message.
+    pub content: String,
+    /// Tool call that this message is responding to.
+    pub tool_call_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionResponse {
+    /// A unique identifier for the chat completion.
+    pub id: String,
+    /// A list of chat completion choices. Can be more than one if n is greater than 1.
+    pub choices: Vec<ChatCompletionChoice>,
+    /// The Unix timestamp (in seconds) of when the chat completion was created.
+    pub created: u64,
+    /// The model used for the chat completion.
+    pub model: String,
+    /// This fingerprint represents the backend configuration that the model runs with.
+    #[serde(default)]
+    pub system_fingerprint: Option<String>,
+    /// The object type, which is always chat.completion.
+    pub object: String,
+    /// Usage statistics for the completion request.
+    #[serde(default)]
+    pub usage: Option<ChatCompletionUsage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionChoice {
+    /// The reason the model stopped generating tokens.
+    pub finish_reason: FinishReason,
+    /// The index of the choice in the list of choices.
+    pub index: usize,
+    /// A chat completion message generated by the model.
+    pub message: AssistantMessage,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinishReason {
+    /// The model hit a natural stop point or a provided stop sequence.
+    Stop,
+    /// The maximum number of tokens specified in the request was reached.
+    Length,
+    /// Content was omitted due to a flag from our content filters.
+    ContentFilter,
+    /// The model called a tool.
+    ToolCalls,
+    /// The model called a function, with the deprecated `functions` parameter.
+    FunctionCall,
+    /// A reason this SDK does not know yet.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatCompletionUsage {
+    /// Number of tokens in the generated completion.
+    pub completion_tokens: usize,
+    /// Number of tokens in the prompt.
+    pub prompt_tokens: usize,
+    /// Total number of tokens used in the request (prompt + completion).
+    pub total_tokens: usize,
+}
+
+/// A stream of chat completion chunks, as returned by `LLMSDK::chat_completion_stream`.
+pub type ChatCompletionStream = BoxStream<'static, Result<ChatCompletionChunk>>;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionChunk {
+    /// A unique identifier for the chat completion. Each chunk has the same ID.
+    pub id: String,
+    /// A list of chat completion choices. Can be empty for the last chunk if stream_options.include_usage is set.
+    pub choices: Vec<ChatCompletionChunkChoice>,
+    /// The Unix timestamp (in seconds) of when the chat completion was created. Each chunk has the same timestamp.
+    pub created: u64,
+    /// The model to generate the completion.
+    pub model: String,
+    /// This fingerprint represents the backend configuration that the model runs with.
+    #[serde(default)]
+    pub system_fingerprint: Option<String>,
+    /// The object type, which is always chat.completion.chunk.
+    pub object: String,
+    /// Usage statistics for the entire request, only present on the last chunk when stream_options.include_usage is set.
+    #[serde(default)]
+    pub usage: Option<ChatCompletionUsage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionChunkChoice {
+    /// A chat completion delta generated by streamed model responses.
+    pub delta: ChatCompletionDelta,
+    /// The reason the model stopped generating tokens, only present on the last chunk of the choice.
+    #[serde(default)]
+    pub finish_reason: Option<FinishReason>,
+    /// The index of the choice in the list of choices.
+    pub index: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChatCompletionDelta {
+    /// The role of the author of this message, only present on the first chunk.
+    #[serde(default)]
+    pub role: Option<String>,
+    /// The contents of the chunk message.
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Partial tool calls. The id and name arrive with the first delta of a call, the arguments are split across deltas.
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCallDelta>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ToolCallDelta {
+    /// The position of the tool call in the message's tool_calls.
+    pub index: usize,
+    /// The ID of the tool call.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// The type of the tool. Currently, only function is supported.
+    #[serde(default)]
+    pub r#type: Option<ToolType>,
+    /// The partial function call.
+    #[serde(default)]
+    pub function: Option<FunctionCallDelta>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FunctionCallDelta {
+    /// The name of the function to call.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// A fragment of the arguments to call the function with.
+    #[serde(default)]
+    pub arguments: Option<String>,
+}
+
+/// Folds streamed chunks back into the `ChatCompletionResponse` a non-streaming call would return.
+#[derive(Debug, Default)]
+pub struct ChatCompletionAccumulator {
+    id: String,
+    created: u64,
+    model: String,
+    system_fingerprint: Option<String>,
+    usage: Option<ChatCompletionUsage>,
+    choices: BTreeMap<usize, ChoiceState>,
+}
+
+#[derive(Debug, Default)]
+struct ChoiceState {
+    message: AssistantMessage,
+    tool_calls: BTreeMap<usize, ToolCall>,
+    finish_reason: Option<FinishReason>,
+}
+
+impl ChatCompletionAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, chunk: &ChatCompletionChunk) {
+        self.id.clone_from(&chunk.id);
+        self.created = chunk.created;
+        self.model.clone_from(&chunk.model);
+        if chunk.system_fingerprint.is_some() {
+            self.system_fingerprint.clone_from(&chunk.system_fingerprint);
+        }
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage;
+        }
+        for choice in &chunk.choices {
+            let state = self.choices.entry(choice.index).or_default();
+            if let Some(content) = &choice.delta.content {
+                state.message.content.get_or_insert_with(String::new).push_str(content);
+            }
+            for delta in &choice.delta.tool_calls {
+                let call = state.tool_calls.entry(delta.index).or_insert_with(|| ToolCall {
+                    id: String::new(),
+                    r#type: ToolType::Function,
+                    function: FunctionCall::default(),
+                });
+                if let Some(id) = &delta.id {
+                    call.id.clone_from(id);
+                }
+                if let Some(function) = &delta.function {
+                    if let Some(name) = &function.name {
+                        call.function.name.push_str(name);
+                    }
+                    if let Some(arguments) = &function.arguments {
+                        call.function.arguments.push_str(arguments);
+                    }
+                }
+            }
+            if choice.finish_reason.is_some() {
+                state.finish_reason = choice.finish_reason;
+            }
+        }
+    }
+
+    pub fn finish(self) -> Result<ChatCompletionResponse> {
+        let choices = self
+            .choices
+            .into_iter()
+            .map(|(index, state)| {
+                let finish_reason = state.finish_reason
+                    .ok_or_else(|| LlmError::IncompleteStream(format!("choice {index} has no finish_reason")))?;
+                let message = AssistantMessage {
+                    tool_calls: state.tool_calls.into_values().collect(),
+                    ..state.message
+                };
+                Ok(ChatCompletionChoice { finish_reason, index, message })
+            })
+            .collect::<Result<_>>()?;
+        Ok(ChatCompletionResponse {
+            id: self.id,
+            choices,
+            created: self.created,
+            model: self.model,
+            system_fingerprint: self.system_fingerprint,
+            object: "chat.completion".into(),
+            usage: self.usage,
+        })
+    }
+}
+
+impl ChatCompletionResponse {
+    /// Drains a chunk stream and folds it into a full response.
+    pub async fn from_stream(stream: impl Stream<Item = Result<ChatCompletionChunk>>) -> Result<Self> {
+        let mut stream = std::pin::pin!(stream);
+        let mut acc = ChatCompletionAccumulator::new();
+        while let Some(chunk) = stream.next().await {
+            acc.push(&chunk?);
+        }
+        acc.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LLMSDK;
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[test]
+    fn test_chat_completion_request_serialize() -> Result<()> {
+        let req = ChatCompletionRequest {
+            temperature: Some(0.5),
+            stop: Some(vec!["\n".into()]),
+            logit_bias: Some(HashMap::from([(50256, -100.0)])),
+            ..ChatCompletionRequest::new(
+                ChatCompletionModel::Gpt4o,
+                [
+                    ChatCompletionMessage::system("You are a helpful assistant."),
+                    ChatCompletionMessage::user("Hello!"),
+                    ChatCompletionMessage::assistant("Hi, how can I help?"),
+                    ChatCompletionMessage::tool("call_1", "42"),
+                ],
+            )
+        };
+        assert_eq!(
+            serde_json::to_value(req)?,
+            json!({
+                "messages": [
+                    { "role": "system", "content": "You are a helpful assistant." },
+                    { "role": "user", "content": "Hello!" },
+                    { "role": "assistant", "content": "Hi, how can I help?" },
+                    { "role": "tool", "content": "42", "tool_call_id": "call_1" },
+                ],
+                "model": "gpt-4o",
+                "logit_bias": { "50256": -100.0 },
+                "stop": ["\n"],
+                "temperature": 0.5,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_chat_completion_custom_model_serialize() -> Result<()> {
+        let req = ChatCompletionRequest::new(
+            ChatCompletionModel::Other("ft:gpt-4o-mini:acme::abc123".into()),
+            [ChatCompletionMessage::user("Hello!")],
+        );
+        assert_eq!(
+            serde_json::to_value(req)?["model"],
+            json!("ft:gpt-4o-mini:acme::abc123")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_chat_completion_response_deserialize() -> Result<()> {
+        let res: ChatCompletionResponse = serde_json::from_value(json!({
+            "id": "chatcmpl-123",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "gpt-4o-mini",
+            "system_fingerprint": "fp_44709d6fcb",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hello there, how may I assist you today?" },
+                "logprobs": null,
+                "finish_reason": "stop"
+            }],
+            "usage": { "prompt_tokens": 9, "completion_tokens": 12, "total_tokens": 21 }
+        }))?;
+        assert_eq!(res.choices.len(), 1);
+        assert_eq!(res.choices[0].finish_reason, FinishReason::Stop);
+        assert_eq!(
+            res.choices[0].message.content.as_deref(),
+            Some("Hello there, how may I assist you today?")
+        );
+        assert_eq!(res.usage.map(|u| u.total_tokens), Some(21));
+
+        let reasons: Vec<FinishReason> = serde_json::from_value(json!(["function_call", "paused_for_review"]))?;
+        assert_eq!(reasons, [FinishReason::FunctionCall, FinishReason::Unknown]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_chunks_fold() -> Result<()> {
+        let chunks = [
+            json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [{"index": 0, "delta": {"role": "assistant", "content": ""}, "finish_reason": null}]}),
+            json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [{"index": 0, "delta": {"content": "Hel"}, "finish_reason": null}]}),
+            json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [{"index": 0, "delta": {"content": "lo"}, "finish_reason": null}]}),
+            json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [{"index": 0, "delta": {}, "finish_reason": "stop"}]}),
+            json!({"id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+                "choices": [], "usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}}),
+        ];
+        let chunks = chunks
+            .into_iter()
+            .map(serde_json::from_value::<ChatCompletionChunk>)
+            .collect::<Result<Vec<_>, _>>()?;
+        let stream = futures_util::stream::iter(chunks.into_iter().map(Ok));
+        let res = ChatCompletionResponse::from_stream(stream).await?;
+        assert_eq!(res.id, "chatcmpl-1");
+        assert_eq!(res.choices.len(), 1);
+        assert_eq!(res.choices[0].message.content.as_deref(), Some("Hello"));
+        assert_eq!(res.choices[0].finish_reason, FinishReason::Stop);
+        assert_eq!(res.usage.map(|u| u.total_tokens), Some(7));
+        Ok(())
+    }
+
+    #[test]
+    fn test_chat_completion_tool_call_deltas_fold() -> Result<()> {
+        let mut acc = ChatCompletionAccumulator::new();
+        for choice in [
+            json!({"index": 0, "delta": {"role": "assistant", "tool_calls": [
+                {"index": 0, "id": "call_1", "type": "function", "function": {"name": "get_weather", "arguments": ""}}]}}),
+            json!({"index": 0, "delta": {"tool_calls": [{"index": 0, "function": {"arguments": "{\"location\":"}}]}}),
+            json!({"index": 0, "delta": {"tool_calls": [{"index": 0, "function": {"arguments": "\"Paris\"}"}}]}}),
+            json!({"index": 0, "delta": {}, "finish_reason": "tool_calls"}),
+        ] {
+            acc.push(&serde_json::from_value(json!({
+                "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o",
+                "choices": [choice]
+            }))?);
+        }
+        let res = acc.finish()?;
+        assert_eq!(res.choices[0].finish_reason, FinishReason::ToolCalls);
+        assert_eq!(res.choices[0].message.content, None);
+        let call = &res.choices[0].message.tool_calls[0];
+        assert_eq!(call.id, "call_1");
+        assert_eq!(call.function.name, "get_weather");
+        assert_eq!(call.function.arguments, "{\"location\":\"Paris\"}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_chat_completion_unfinished_stream() -> Result<()> {
+        let mut acc = ChatCompletionAccumulator::new();
+        acc.push(&serde_json::from_value(json!({
+            "id": "chatcmpl-1", "object": "chat.completion.chunk", "created": 1, "model": "gpt-4o-mini",
+            "choices": [{"index": 0, "delta": {"content": "Hel"}, "finish_reason": null}]
+        }))?);
+        assert!(acc.finish().is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_chat_completion_stream() -> Result<()> {
+        let sdk = LLMSDK::new(std::env::var("OPENAI_API_KEY")?);
+        let req = ChatCompletionRequest::new(
+            ChatCompletionModel::Gpt4oMini,
+            [ChatCompletionMessage::user("Count from 1 to 5.")],
+        );
+        let mut stream = sdk.chat_completion_stream(req).await?;
+        while let Some(chunk) = stream.next().await {
+            print!("{}", chunk?.choices[0].delta.content.as_deref().unwrap_or_default());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_chat_completion_response() -> Result<()> {
+        let sdk = LLMSDK::new(std::env::var("OPENAI_API_KEY")?);
+        let req = ChatCompletionRequest::new(
+            ChatCompletionModel::Gpt4oMini,
+            [
+                ChatCompletionMessage::system("You are a helpful assistant."),
+                ChatCompletionMessage::user("Say hello in one word."),
+            ],
+        );
+        let res = sdk.chat_completion(req).await?;
+        assert_eq!(res.choices.len(), 1);
+        println!("chat completion: {:?}", res);
+        Ok(())
+    }
+}
@@ -0,0 +1,355 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Deserializer, Serialize};
+use reqwest::RequestBuilder;
+
+use crate::{IntoRequest, RequestContext};
+
+/// The maximum number of inputs OpenAI accepts in one embedding request.
+pub const MAX_EMBEDDING_INPUTS: usize = 2048;
+/// The maximum number of tokens summed over all inputs of one embedding request.
+pub const MAX_EMBEDDING_TOKENS: usize = 300_000;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateEmbeddingRequest {
+    /// Input text to embed, encoded as a string or array of tokens. To embed multiple inputs in a single request, pass an array of strings or array of token arrays.
+    pub input: EmbeddingInput,
+    /// ID of the model to use.
+    pub model: EmbeddingModel,
+    /// The number of dimensions the resulting output embeddings should have. Only supported in text-embedding-3 and later models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<usize>,
+    /// The format to return the embeddings in. Can be either float or base64. Either way the response holds decoded vectors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding_format: Option<EmbeddingEncodingFormat>,
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum EmbeddingInput {
+    String(String),
+    StringArray(Vec<String>),
+    Tokens(Vec<u32>),
+    TokensArray(Vec<Vec<u32>>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub enum EmbeddingModel {
+    #[default]
+    #[serde(rename = "text-embedding-3-small")]
+    TextEmbedding3Small,
+    #[serde(rename = "text-embedding-3-large")]
+    TextEmbedding3Large,
+    #[serde(rename = "text-embedding-ada-002")]
+    TextEmbeddingAda002,
+    /// Any other model id.
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingEncodingFormat {
+    #[default]
+    Float,
+    /// Smaller on the wire; decoded back into floats when the response is read.
+    Base64,
+}
+
+impl Default for EmbeddingInput {
+    fn default() -> Self {
+        EmbeddingInput::StringArray(vec![])
+    }
+}
+
+impl EmbeddingInput {
+    /// The number of inputs, i.e. the number of embeddings the request returns.
+    pub fn len(&self) -> usize {
+        match self {
+            EmbeddingInput::String(_) | EmbeddingInput::Tokens(_) => 1,
+            EmbeddingInput::StringArray(v) => v.len(),
+            EmbeddingInput::TokensArray(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<&str> for EmbeddingInput {
+    fn from(s: &str) -> Self {
+        EmbeddingInput::String(s.to_string())
+    }
+}
+
+impl From<String> for EmbeddingInput {
+    fn from(s: String) -> Self {
+        EmbeddingInput::String(s)
+    }
+}
+
+impl From<Vec<String>> for EmbeddingInput {
+    fn from(v: Vec<String>) -> Self {
+        EmbeddingInput::StringArray(v)
+    }
+}
+
+impl From<Vec<&str>> for EmbeddingInput {
+    fn from(v: Vec<&str>) -> Self {
+        EmbeddingInput::StringArray(v.into_iter().map(String::from).collect())
+    }
+}
+
+impl From<Vec<u32>> for EmbeddingInput {
+    fn from(v: Vec<u32>) -> Self {
+        EmbeddingInput::Tokens(v)
+    }
+}
+
+impl From<Vec<Vec<u32>>> for EmbeddingInput {
+    fn from(v: Vec<Vec<u32>>) -> Self {
+        EmbeddingInput::TokensArray(v)
+    }
+}
+
+impl CreateEmbeddingRequest {
+    pub fn new(model: EmbeddingModel, input: impl Into<EmbeddingInput>) -> Self {
+        CreateEmbeddingRequest {
+            input: input.into(),
+            model,
+            ..Default::default()
+        }
+    }
+
+    /// Splits the request into requests of at most `max_inputs` inputs and `max_tokens` tokens
+    /// each. A text input is counted as one token per byte, which the byte-level tokenizers
+    /// never exceed, so batches of text are often smaller than they could be. A single input is
+    /// never split.
+    pub fn split(self, max_inputs: usize, max_tokens: usize) -> Vec<Self> {
+        let CreateEmbeddingRequest { input, model, dimensions, encoding_format, user } = self;
+        let max_inputs = max_inputs.max(1);
+        let batches: Vec<EmbeddingInput> = match input {
+            EmbeddingInput::StringArray(inputs) => {
+                batch(inputs, max_inputs, max_tokens, |s| s.len())
+                    .into_iter()
+                    .map(EmbeddingInput::StringArray)
+                    .collect()
+            }
+            EmbeddingInput::TokensArray(inputs) => {
+                batch(inputs, max_inputs, max_tokens, |t| t.len())
+                    .into_iter()
+                    .map(EmbeddingInput::TokensArray)
+                    .collect()
+            }
+            input => vec![input],
+        };
+        batches
+            .into_iter()
+            .map(|input| CreateEmbeddingRequest {
+                input,
+                model: model.clone(),
+                dimensions,
+                encoding_format,
+                user: user.clone(),
+            })
+            .collect()
+    }
+}
+
+fn batch<T>(inputs: Vec<T>, max_inputs: usize, max_tokens: usize, tokens: impl Fn(&T) -> usize) -> Vec<Vec<T>> {
+    let mut batches = vec![];
+    let mut current = vec![];
+    let mut current_tokens = 0;
+    for input in inputs {
+        let n = tokens(&input);
+        if !current.is_empty() && (current.len() == max_inputs || current_tokens + n > max_tokens) {
+            batches.push(std::mem::take(&mut current));
+            current_tokens = 0;
+        }
+        current_tokens += n;
+        current.push(input);
+    }
+    if !current.is_empty() || batches.is_empty() {
+        batches.push(current);
+    }
+    batches
+}
+
+impl IntoRequest for CreateEmbeddingRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("embeddings")
+           .json(&self)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateEmbeddingResponse {
+    /// The object type, which is always list.
+    pub object: String,
+    /// The list of embeddings generated by the model, one per input.
+    pub data: Vec<EmbeddingData>,
+    /// The name of the model used to generate the embedding.
+    pub model: String,
+    /// The usage information for the request.
+    pub usage: EmbeddingUsage,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddingData {
+    /// The index of the input this embedding belongs to.
+    pub index: usize,
+    /// The embedding vector.
+    #[serde(deserialize_with = "deserialize_embedding")]
+    pub embedding: Vec<f32>,
+    /// The object type, which is always embedding.
+    pub object: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct EmbeddingUsage {
+    /// The number of tokens used by the prompt.
+    pub prompt_tokens: usize,
+    /// The total number of tokens used by the request.
+    pub total_tokens: usize,
+}
+
+impl CreateEmbeddingResponse {
+    /// Appends the response of the next batch, shifting its indices past the current ones.
+    pub(crate) fn merge(&mut self, other: CreateEmbeddingResponse) {
+        let offset = self.data.len();
+        self.data.extend(other.data.into_iter().map(|mut data| {
+            data.index += offset;
+            data
+        }));
+        self.usage.prompt_tokens += other.usage.prompt_tokens;
+        self.usage.total_tokens += other.usage.total_tokens;
+    }
+}
+
+/// Accepts both encodings: an array of floats, or base64 of little-endian f32s.
+fn deserialize_embedding<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f32>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Embedding {
+        Float(Vec<f32>),
+        Base64(String),
+    }
+
+    match Embedding::deserialize(deserializer)? {
+        Embedding::Float(v) => Ok(v),
+        Embedding::Base64(s) => {
+            let bytes = STANDARD.decode(s).map_err(serde::de::Error::custom)?;
+            if bytes.len() % 4 != 0 {
+                return Err(serde::de::Error::custom("base64 embedding is not a whole number of f32"));
+            }
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[test]
+    fn test_embedding_request_serialize() -> Result<()> {
+        let req = CreateEmbeddingRequest {
+            dimensions: Some(256),
+            encoding_format: Some(EmbeddingEncodingFormat::Base64),
+            ..CreateEmbeddingRequest::new(EmbeddingModel::TextEmbedding3Small, vec!["hello", "world"])
+        };
+        assert_eq!(
+            serde_json::to_value(req)?,
+            json!({
+                "input": ["hello", "world"],
+                "model": "text-embedding-3-small",
+                "dimensions": 256,
+                "encoding_format": "base64",
+            })
+        );
+        let req = CreateEmbeddingRequest::new(EmbeddingModel::TextEmbeddingAda002, vec![vec![1u32, 2], vec![3]]);
+        assert_eq!(serde_json::to_value(req)?["input"], json!([[1, 2], [3]]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_embedding_response_deserialize_base64() -> Result<()> {
+        let bytes: Vec<u8> = [1.0f32, -0.5].iter().flat_map(|f| f.to_le_bytes()).collect();
+        let res: CreateEmbeddingResponse = serde_json::from_value(json!({
+            "object": "list",
+            "data": [
+                { "object": "embedding", "index": 0, "embedding": STANDARD.encode(bytes) },
+                { "object": "embedding", "index": 1, "embedding": [0.25, 0.5] },
+            ],
+            "model": "text-embedding-3-small",
+            "usage": { "prompt_tokens": 2, "total_tokens": 2 }
+        }))?;
+        assert_eq!(res.data[0].embedding, vec![1.0, -0.5]);
+        assert_eq!(res.data[1].embedding, vec![0.25, 0.5]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_embedding_request_split() {
+        let inputs: Vec<String> = (0..5).map(|i| format!("input {i}")).collect();
+        let req = CreateEmbeddingRequest::new(EmbeddingModel::TextEmbedding3Small, inputs);
+        let batches = req.clone().split(2, usize::MAX);
+        assert_eq!(batches.iter().map(|r| r.input.len()).collect::<Vec<_>>(), vec![2, 2, 1]);
+        // "input N" is counted as 7 tokens
+        let batches = req.split(10, 14);
+        assert_eq!(batches.iter().map(|r| r.input.len()).collect::<Vec<_>>(), vec![2, 2, 1]);
+
+        let req = CreateEmbeddingRequest::new(EmbeddingModel::TextEmbedding3Small, "single");
+        assert_eq!(req.split(1, 1).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_embedding_batches() -> Result<()> {
+        let batch = json!({
+            "object": "list",
+            "data": [
+                { "object": "embedding", "index": 0, "embedding": [0.1] },
+                { "object": "embedding", "index": 1, "embedding": [0.2] },
+            ],
+            "model": "text-embedding-3-small",
+            "usage": { "prompt_tokens": 4, "total_tokens": 4 }
+        });
+        let server = MockServer::start(vec![MockResponse::json(batch)]).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let inputs: Vec<String> = (0..4).map(|i| i.to_string()).collect();
+        let req = CreateEmbeddingRequest::new(EmbeddingModel::TextEmbedding3Small, inputs);
+        let res = sdk.create_embedding_batched(req, 2, MAX_EMBEDDING_TOKENS).await?;
+
+        assert_eq!(res.data.iter().map(|d| d.index).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(res.usage.total_tokens, 8);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].path, "/embeddings");
+        assert_eq!(requests[1].json()["input"], json!(["2", "3"]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_create_embedding() -> Result<()> {
+        let sdk = LLMSDK::new(std::env::var("OPENAI_API_KEY")?);
+        let req = CreateEmbeddingRequest::new(EmbeddingModel::TextEmbedding3Small, vec!["hello", "world"]);
+        let res = sdk.create_embedding(req).await?;
+        assert_eq!(res.data.len(), 2);
+        assert_eq!(res.data[0].embedding.len(), 1536);
+        Ok(())
+    }
+}
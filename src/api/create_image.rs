@@ -1,24 +1,40 @@
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
-use reqwest::{RequestBuilder, Client};
+use reqwest::RequestBuilder;
 
-use crate::IntoRequest;
+use crate::{check_status, IntoRequest, LlmError, RequestContext, Result, LLMSDK};
 
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct CreateImageRequest {
-    /// A text description of the desired image(s). The maximum length is 4000 characters for dall-e-3.
+    /// A text description of the desired image(s). The maximum length is 1000 characters for dall-e-2, 4000 characters for dall-e-3 and 32000 characters for gpt-image-1.
     prompt: String,
-    /// The model to use for image generation. Only support Dall-e-3
+    /// The model to use for image generation. One of dall-e-2, dall-e-3 or gpt-image-1.
     model: ImageModel,
+    /// Allows to set transparency for the background of the generated image(s). This param is only supported for gpt-image-1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background: Option<ImageBackground>,
+    /// Control the content-moderation level for images generated by gpt-image-1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    moderation: Option<ImageModeration>,
     /// The number of images to generate. Must be between 1 and 10. For dall-e-3, only n=1 is supported.
     #[serde(skip_serializing_if = "Option::is_none")]
     n: Option<usize>,
-    /// The quality of the image that will be generated. hd creates images with finer details and greater consistency across the image. This param is only supported for dall-e-3.
+    /// The compression level (0-100%) for the generated images. This parameter is only supported for gpt-image-1 with the webp or jpeg output formats.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_compression: Option<u8>,
+    /// The format in which the generated images are returned. This parameter is only supported for gpt-image-1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_format: Option<ImageOutputFormat>,
+    /// The quality of the image that will be generated. hd and standard are supported for dall-e-3, low, medium, high and auto for gpt-image-1; dall-e-2 only supports standard.
     #[serde(skip_serializing_if = "Option::is_none")]
     quality: Option<ImageQuality>,
-    /// The format in which the generated images are returned. Must be one of url or b64_json.
+    /// The format in which the generated images are returned. Must be one of url or b64_json. This parameter isn't supported for gpt-image-1, which always returns b64_json.
     #[serde(skip_serializing_if = "Option::is_none")]
     response_format: Option<ImageResponseFormat>,
-    /// The size of the generated images. Must be one of 1024x1024, 1792x1024, or 1024x1792 for dall-e-3 models.
+    /// The size of the generated images. Must be one of 256x256, 512x512, or 1024x1024 for dall-e-2, one of 1024x1024, 1792x1024, or 1024x1792 for dall-e-3, and one of 1024x1024, 1536x1024, 1024x1536 or auto for gpt-image-1.
     #[serde(skip_serializing_if = "Option::is_none")]
     size: Option<ImageSize>,
     /// The style of the generated images. Must be one of vivid or natural. Vivid causes the model to lean towards generating hyper-real and dramatic images. Natural causes the model to produce more natural, less hyper-real looking images. This param is only supported for dall-e-3.
@@ -36,46 +52,305 @@ impl CreateImageRequest {
             ..Default::default()
         }
     }
+
+    /// Starts a request with the given prompt. The other settings keep the API defaults unless set.
+    pub fn builder(prompt: impl Into<String>) -> CreateImageRequestBuilder {
+        CreateImageRequestBuilder {
+            req: CreateImageRequest::new(prompt),
+        }
+    }
+
+    /// Checks the request against what its model supports, so an invalid combination fails
+    /// before anything is sent.
+    pub fn validate(&self) -> Result<()> {
+        let model = self.model;
+        let invalid = |msg: String| Err(LlmError::Validation(msg));
+        if self.prompt.trim().is_empty() {
+            return invalid("prompt must not be empty".into());
+        }
+        let prompt_len = self.prompt.chars().count();
+        if prompt_len > model.max_prompt_len() {
+            return invalid(format!(
+                "prompt is {prompt_len} characters, {model} allows at most {}",
+                model.max_prompt_len()
+            ));
+        }
+        if let Some(n) = self.n {
+            if n == 0 || n > model.max_n() {
+                return invalid(format!("n must be between 1 and {} for {model}", model.max_n()));
+            }
+        }
+        if let Some(size) = self.size {
+            if !model.sizes().contains(&size) {
+                return invalid(format!("size {size:?} is not supported by {model}"));
+            }
+        }
+        if let Some(quality) = self.quality {
+            if !model.qualities().contains(&quality) {
+                return invalid(format!("quality {quality:?} is not supported by {model}"));
+            }
+        }
+        if self.style.is_some() && model != ImageModel::DallE3 {
+            return invalid(format!("style is not supported by {model}"));
+        }
+        if model == ImageModel::GptImage1 {
+            if self.response_format.is_some() {
+                return invalid("gpt-image-1 always returns b64_json, response_format is not supported".into());
+            }
+        } else {
+            let gpt_image_only = [
+                ("background", self.background.is_some()),
+                ("moderation", self.moderation.is_some()),
+                ("output_compression", self.output_compression.is_some()),
+                ("output_format", self.output_format.is_some()),
+            ];
+            if let Some((name, _)) = gpt_image_only.iter().find(|(_, set)| *set) {
+                return invalid(format!("{name} is only supported by gpt-image-1"));
+            }
+        }
+        let output_format = self.output_format.unwrap_or_default();
+        if let Some(compression) = self.output_compression {
+            if compression > 100 {
+                return invalid("output_compression must be between 0 and 100".into());
+            }
+            if output_format == ImageOutputFormat::Png {
+                return invalid("output_compression requires the jpeg or webp output format".into());
+            }
+        }
+        if self.background == Some(ImageBackground::Transparent) && output_format == ImageOutputFormat::Jpeg {
+            return invalid("a transparent background requires the png or webp output format".into());
+        }
+        Ok(())
+    }
+}
+
+/// Configures a `CreateImageRequest`. Created with `CreateImageRequest::builder()`.
+#[derive(Debug, Clone)]
+pub struct CreateImageRequestBuilder {
+    req: CreateImageRequest,
+}
+
+impl CreateImageRequestBuilder {
+    pub fn model(mut self, model: ImageModel) -> Self {
+        self.req.model = model;
+        self
+    }
+
+    /// Must be between 1 and 10, and 1 for dall-e-3.
+    pub fn n(mut self, n: usize) -> Self {
+        self.req.n = Some(n);
+        self
+    }
+
+    pub fn quality(mut self, quality: ImageQuality) -> Self {
+        self.req.quality = Some(quality);
+        self
+    }
+
+    /// Not supported by gpt-image-1, which always returns b64_json.
+    pub fn response_format(mut self, response_format: ImageResponseFormat) -> Self {
+        self.req.response_format = Some(response_format);
+        self
+    }
+
+    pub fn size(mut self, size: ImageSize) -> Self {
+        self.req.size = Some(size);
+        self
+    }
+
+    /// Only supported by dall-e-3.
+    pub fn style(mut self, style: ImageStyle) -> Self {
+        self.req.style = Some(style);
+        self
+    }
+
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.req.user = Some(user.into());
+        self
+    }
+
+    /// Only supported by gpt-image-1.
+    pub fn background(mut self, background: ImageBackground) -> Self {
+        self.req.background = Some(background);
+        self
+    }
+
+    /// Only supported by gpt-image-1.
+    pub fn moderation(mut self, moderation: ImageModeration) -> Self {
+        self.req.moderation = Some(moderation);
+        self
+    }
+
+    /// Only supported by gpt-image-1.
+    pub fn output_format(mut self, output_format: ImageOutputFormat) -> Self {
+        self.req.output_format = Some(output_format);
+        self
+    }
+
+    /// Only supported by gpt-image-1 with the jpeg or webp output format.
+    pub fn output_compression(mut self, output_compression: u8) -> Self {
+        self.req.output_compression = Some(output_compression);
+        self
+    }
+
+    /// Fails with `LlmError::Validation` when the settings are not valid for the model.
+    pub fn build(self) -> Result<CreateImageRequest> {
+        self.req.validate()?;
+        Ok(self.req)
+    }
 }
 
 impl IntoRequest for CreateImageRequest {
-    fn into_request(self, client: Client) -> RequestBuilder {
-        client.post("https://api.openai.com/v1/images/generations")
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("images/generations")
            .json(&self)
     }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct CreateImageResponse {
-    created: u64,
-    data: Vec<ImageObject>
+    /// The Unix timestamp (in seconds) of when the images were created.
+    pub created: u64,
+    /// The generated images.
+    pub data: Vec<ImageObject>,
+    /// Token usage of the request. Only returned for gpt-image-1.
+    #[serde(default)]
+    pub usage: Option<ImageUsage>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct ImageUsage {
+    /// The number of tokens (images and text) in the input prompt.
+    pub input_tokens: usize,
+    /// The number of image tokens in the output image.
+    pub output_tokens: usize,
+    /// The total number of tokens (images and text) used for the image generation.
+    pub total_tokens: usize,
+    /// The input tokens detailed information for the image generation.
+    #[serde(default)]
+    pub input_tokens_details: Option<ImageInputTokensDetails>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct ImageInputTokensDetails {
+    /// The number of text tokens in the input prompt.
+    pub text_tokens: usize,
+    /// The number of image tokens in the input prompt.
+    pub image_tokens: usize,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
-enum ImageModel {
+pub enum ImageModel {
+    #[serde(rename = "dall-e-2")]
+    DallE2,
     #[default]
     #[serde(rename = "dall-e-3")]
-    DallE3
+    DallE3,
+    #[serde(rename = "gpt-image-1")]
+    GptImage1,
+}
+
+impl ImageModel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageModel::DallE2 => "dall-e-2",
+            ImageModel::DallE3 => "dall-e-3",
+            ImageModel::GptImage1 => "gpt-image-1",
+        }
+    }
+
+    fn max_prompt_len(&self) -> usize {
+        match self {
+            ImageModel::DallE2 => 1000,
+            ImageModel::DallE3 => 4000,
+            ImageModel::GptImage1 => 32000,
+        }
+    }
+
+    fn max_n(&self) -> usize {
+        match self {
+            ImageModel::DallE3 => 1,
+            ImageModel::DallE2 | ImageModel::GptImage1 => 10,
+        }
+    }
+
+    fn sizes(&self) -> &'static [ImageSize] {
+        use ImageSize::*;
+        match self {
+            ImageModel::DallE2 => &[Small, Medium, Large],
+            ImageModel::DallE3 => &[Large, LargeWide, LargeTall],
+            ImageModel::GptImage1 => &[Large, Landscape, Portrait, Auto],
+        }
+    }
+
+    fn qualities(&self) -> &'static [ImageQuality] {
+        use ImageQuality::*;
+        match self {
+            ImageModel::DallE2 => &[Standard],
+            ImageModel::DallE3 => &[Standard, Hd],
+            ImageModel::GptImage1 => &[Low, Medium, High, Auto],
+        }
+    }
+}
+
+impl std::fmt::Display for ImageModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
-enum ImageQuality {
+pub enum ImageQuality {
     #[default]
     Standard,
     Hd,
+    Low,
+    Medium,
+    High,
+    Auto,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
-enum ImageResponseFormat {
+pub enum ImageBackground {
+    Transparent,
+    Opaque,
+    #[default]
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageModeration {
+    Low,
+    #[default]
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageOutputFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageResponseFormat {
     #[default]
     Url,
     B64Json,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
-enum ImageSize {
+pub enum ImageSize {
+    #[serde(rename = "256x256")]
+    Small,
+    #[serde(rename = "512x512")]
+    Medium,
     #[default]
     #[serde(rename = "1024x1024")]
     Large,
@@ -83,28 +358,103 @@ enum ImageSize {
     LargeWide,
     #[serde(rename = "1024x1792")]
     LargeTall,
+    #[serde(rename = "1536x1024")]
+    Landscape,
+    #[serde(rename = "1024x1536")]
+    Portrait,
+    #[serde(rename = "auto")]
+    Auto,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
-enum ImageStyle {
+pub enum ImageStyle {
     #[default]
     Vivid,
     Natural,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct ImageObject {
-    b64_json: Option<String>,
-    url: Option<String>,
-    revised_prompt: String,
+pub struct ImageObject {
+    /// The base64-encoded JSON of the generated image, if response_format is b64_json.
+    pub b64_json: Option<String>,
+    /// The URL of the generated image, if response_format is url (default). URLs expire after an hour.
+    pub url: Option<String>,
+    /// The prompt that was used to generate the image, if there was any revision to the prompt.
+    #[serde(default)]
+    pub revised_prompt: Option<String>,
+}
+
+impl ImageObject {
+    /// The image file, downloaded from `url` or decoded from `b64_json`. The download goes
+    /// through the SDK's client and timeout, but sends none of its headers: the url points at
+    /// a CDN, which must not see the token, Azure's `api-key` or any other custom header.
+    pub async fn bytes(&self, sdk: &LLMSDK) -> Result<Bytes> {
+        match (&self.b64_json, &self.url) {
+            (Some(b64), _) => STANDARD
+                .decode(b64)
+                .map(Bytes::from)
+                .map_err(|e| LlmError::Validation(format!("invalid b64_json: {e}"))),
+            (None, Some(url)) => {
+                let req = sdk.ctx.client().get(url).timeout(sdk.timeout);
+                let res = check_status(req.send().await?).await?;
+                Ok(res.bytes().await?)
+            }
+            (None, None) => Err(LlmError::Validation("image has neither url nor b64_json".into())),
+        }
+    }
+
+    /// Writes the image to `path`. If the path has no extension, the one of the detected
+    /// format is added. Returns the path that was written.
+    pub async fn save_to(&self, sdk: &LLMSDK, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let data = self.bytes(sdk).await?;
+        let mut path = path.as_ref().to_path_buf();
+        if path.extension().is_none() {
+            if let Some(format) = ImageFormat::detect(&data) {
+                path.set_extension(format.extension());
+            }
+        }
+        tokio::fs::write(&path, &data).await?;
+        Ok(path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Webp,
+    Gif,
+}
+
+impl ImageFormat {
+    /// Detects the format from the magic bytes at the start of the file.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match data {
+            [0x89, b'P', b'N', b'G', ..] => Some(ImageFormat::Png),
+            [0xff, 0xd8, 0xff, ..] => Some(ImageFormat::Jpeg),
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some(ImageFormat::Webp),
+            [b'G', b'I', b'F', b'8', ..] => Some(ImageFormat::Gif),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Webp => "webp",
+            ImageFormat::Gif => "gif",
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
-
-    use crate::LLMSDK;
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
 
     use super::*;
     use anyhow::Result;
@@ -143,6 +493,195 @@ mod tests {
         Ok(())
     }
     
+    #[test]
+    fn test_image_request_validate() {
+        let ok = |req: CreateImageRequest| req.validate().is_ok();
+        assert!(ok(CreateImageRequest::new("a cat")));
+        assert!(!ok(CreateImageRequest::new("  ")));
+        assert!(!ok(CreateImageRequest::new("a".repeat(4001))));
+        assert!(ok(CreateImageRequest {
+            model: ImageModel::GptImage1,
+            ..CreateImageRequest::new("a".repeat(4001))
+        }));
+        // dall-e-2: small sizes and up to 10 images, but no style
+        assert!(ok(CreateImageRequest {
+            model: ImageModel::DallE2,
+            size: Some(ImageSize::Small),
+            n: Some(10),
+            ..CreateImageRequest::new("a cat")
+        }));
+        assert!(!ok(CreateImageRequest {
+            model: ImageModel::DallE2,
+            style: Some(ImageStyle::Natural),
+            ..CreateImageRequest::new("a cat")
+        }));
+        // dall-e-3: one image, no small sizes
+        assert!(!ok(CreateImageRequest {
+            n: Some(2),
+            ..CreateImageRequest::new("a cat")
+        }));
+        assert!(!ok(CreateImageRequest {
+            size: Some(ImageSize::Medium),
+            ..CreateImageRequest::new("a cat")
+        }));
+        assert!(!ok(CreateImageRequest {
+            background: Some(ImageBackground::Transparent),
+            ..CreateImageRequest::new("a cat")
+        }));
+        // gpt-image-1: transparency needs png or webp, compression needs jpeg or webp
+        assert!(ok(CreateImageRequest {
+            model: ImageModel::GptImage1,
+            background: Some(ImageBackground::Transparent),
+            quality: Some(ImageQuality::High),
+            ..CreateImageRequest::new("a cat")
+        }));
+        assert!(!ok(CreateImageRequest {
+            model: ImageModel::GptImage1,
+            background: Some(ImageBackground::Transparent),
+            output_format: Some(ImageOutputFormat::Jpeg),
+            ..CreateImageRequest::new("a cat")
+        }));
+        assert!(!ok(CreateImageRequest {
+            model: ImageModel::GptImage1,
+            output_compression: Some(50),
+            ..CreateImageRequest::new("a cat")
+        }));
+        assert!(!ok(CreateImageRequest {
+            model: ImageModel::GptImage1,
+            response_format: Some(ImageResponseFormat::Url),
+            ..CreateImageRequest::new("a cat")
+        }));
+        assert!(!ok(CreateImageRequest {
+            model: ImageModel::GptImage1,
+            quality: Some(ImageQuality::Hd),
+            ..CreateImageRequest::new("a cat")
+        }));
+    }
+
+    #[test]
+    fn test_image_request_builder() -> Result<()> {
+        let req = CreateImageRequest::builder("draw a cute caterpillar")
+            .model(ImageModel::DallE3)
+            .quality(ImageQuality::Hd)
+            .size(ImageSize::LargeTall)
+            .style(ImageStyle::Natural)
+            .response_format(ImageResponseFormat::B64Json)
+            .build()?;
+        assert_eq!(
+            serde_json::to_value(&req)?,
+            json!({
+                "prompt": "draw a cute caterpillar",
+                "model": "dall-e-3",
+                "quality": "hd",
+                "response_format": "b64_json",
+                "size": "1024x1792",
+                "style": "natural",
+            })
+        );
+
+        let invalid = [
+            CreateImageRequest::builder(""),
+            CreateImageRequest::builder("a".repeat(4001)),
+            CreateImageRequest::builder("a cat").n(2),
+        ];
+        for builder in invalid {
+            assert!(matches!(builder.build(), Err(LlmError::Validation(_))));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_gpt_image_request() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(json!({
+            "created": 1,
+            "data": [{ "b64_json": "UE5H" }],
+            "usage": {
+                "total_tokens": 100,
+                "input_tokens": 50,
+                "output_tokens": 50,
+                "input_tokens_details": { "text_tokens": 10, "image_tokens": 40 }
+            }
+        }))])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = CreateImageRequest {
+            model: ImageModel::GptImage1,
+            background: Some(ImageBackground::Transparent),
+            output_format: Some(ImageOutputFormat::Webp),
+            output_compression: Some(80),
+            moderation: Some(ImageModeration::Low),
+            ..CreateImageRequest::new("a game asset")
+        };
+        let res = sdk.create_image(req).await?;
+        assert_eq!(res.usage.unwrap().input_tokens_details.unwrap().image_tokens, 40);
+        assert_eq!(
+            server.requests()[0].json(),
+            json!({
+                "prompt": "a game asset",
+                "model": "gpt-image-1",
+                "background": "transparent",
+                "moderation": "low",
+                "output_compression": 80,
+                "output_format": "webp",
+            })
+        );
+
+        let invalid = CreateImageRequest {
+            n: Some(2),
+            ..CreateImageRequest::new("a cat")
+        };
+        assert!(matches!(sdk.create_image(invalid).await, Err(LlmError::Validation(_))));
+        assert_eq!(server.requests().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_image_object_save_b64() -> Result<()> {
+        let png = b"\x89PNG\r\n\x1a\nrest";
+        let image = ImageObject {
+            b64_json: Some(STANDARD.encode(png)),
+            url: None,
+            revised_prompt: None,
+        };
+        let sdk = LLMSDK::new(String::new());
+        assert_eq!(&image.bytes(&sdk).await?[..], png);
+        let path = image.save_to(&sdk, std::env::temp_dir().join("llm-sdk-save-test")).await?;
+        assert_eq!(path.extension().unwrap(), "png");
+        assert_eq!(tokio::fs::read(&path).await?, png);
+        tokio::fs::remove_file(&path).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_image_object_download_url() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, &b"\xff\xd8\xffjpeg"[..]),
+            MockResponse::new(403, "expired"),
+        ])
+        .await;
+        let image = ImageObject {
+            b64_json: None,
+            url: Some(format!("{}/image.jpg", server.url)),
+            revised_prompt: None,
+        };
+        let sdk = LLMSDK::builder()
+            .base_url("http://127.0.0.1:1")
+            .token("sk-test")
+            .organization("org-test")
+            .project("proj-test")
+            .header("api-key", "azure-key")
+            .build()?;
+        let data = image.bytes(&sdk).await?;
+        assert_eq!(ImageFormat::detect(&data), Some(ImageFormat::Jpeg));
+        let requests = server.requests();
+        for name in ["authorization", "api-key", "openai-organization", "openai-project"] {
+            assert_eq!(requests[0].header(name), None, "{name} was sent to the image host");
+        }
+        let err = image.bytes(&sdk).await.unwrap_err();
+        assert_eq!(err.status(), Some(reqwest::StatusCode::FORBIDDEN));
+        Ok(())
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_image_response_deserialize() -> Result<()> {
@@ -154,13 +693,8 @@ mod tests {
         assert!(image.url.is_some());
         assert!(image.b64_json.is_none());
         println!("image: {:?}", image);
-        fs::write(
-            "/tmp/llm-sdk/caterpillar.png",
-            reqwest::get(image.url.as_ref().unwrap())
-            .await?
-            .bytes()
-            .await?,
-        )?;
+        let path = image.save_to(&sdk, "/tmp/llm-sdk/caterpillar").await?;
+        assert_eq!(path.extension().unwrap(), "png");
         Ok(())
     }
 }
\ No newline at end of file
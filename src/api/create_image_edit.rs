@@ -0,0 +1,95 @@
+use crate::{
+    multipart::{MultipartForm, MultipartRequest},
+    FileSource, ImageResponseFormat, ImageSize, Result,
+};
+
+/// Creates an edited or extended image given an original image and a prompt. Only dall-e-2 is supported.
+#[derive(Debug)]
+pub struct CreateImageEditRequest {
+    /// The image to edit. Must be a valid PNG file, less than 4MB, and square. If mask is not provided, image must have transparency, which will be used as the mask.
+    pub image: FileSource,
+    /// A text description of the desired image(s). The maximum length is 1000 characters.
+    pub prompt: String,
+    /// An additional image whose fully transparent areas (e.g. where alpha is zero) indicate where image should be edited. Must be a valid PNG file, less than 4MB, and have the same dimensions as image.
+    pub mask: Option<FileSource>,
+    /// The number of images to generate. Must be between 1 and 10.
+    pub n: Option<usize>,
+    /// The format in which the generated images are returned. Must be one of url or b64_json.
+    pub response_format: Option<ImageResponseFormat>,
+    /// The size of the generated images. Must be one of 256x256, 512x512, or 1024x1024.
+    pub size: Option<ImageSize>,
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
+    pub user: Option<String>,
+}
+
+impl CreateImageEditRequest {
+    pub fn new(image: impl Into<FileSource>, prompt: impl Into<String>) -> Self {
+        CreateImageEditRequest {
+            image: image.into(),
+            prompt: prompt.into(),
+            mask: None,
+            n: None,
+            response_format: None,
+            size: None,
+            user: None,
+        }
+    }
+
+    pub(crate) async fn into_multipart(self) -> Result<MultipartRequest> {
+        let mut form = MultipartForm::new()
+            .file_source("image", self.image)
+            .await?
+            .text("prompt", self.prompt);
+        if let Some(mask) = self.mask {
+            form = form.file_source("mask", mask).await?;
+        }
+        let form = form
+            .optional("n", self.n)
+            .optional("response_format", self.response_format)
+            .optional("size", self.size)
+            .optional("user", self.user);
+        Ok(MultipartRequest::new("images/edits", form))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_create_image_edit_multipart() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(json!({
+            "created": 1,
+            "data": [{ "url": "https://example.com/edited.png" }]
+        }))])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = CreateImageEditRequest {
+            mask: Some(FileSource::bytes("mask.png", &b"MASK"[..])),
+            n: Some(2),
+            response_format: Some(ImageResponseFormat::Url),
+            ..CreateImageEditRequest::new(FileSource::bytes("cat.png", &b"CAT"[..]), "give the cat a hat")
+        };
+        let res = sdk.create_image_edit(req).await?;
+        assert_eq!(res.data[0].url.as_deref(), Some("https://example.com/edited.png"));
+        assert_eq!(res.data[0].revised_prompt, None);
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/images/edits");
+        assert!(req.header("content-type").unwrap().starts_with("multipart/form-data; boundary="));
+        let body = String::from_utf8_lossy(&req.body);
+        assert!(body.contains("name=\"image\"; filename=\"cat.png\"\r\nContent-Type: image/png\r\n\r\nCAT\r\n"));
+        assert!(body.contains("name=\"mask\"; filename=\"mask.png\""));
+        assert!(body.contains("name=\"prompt\"\r\n\r\ngive the cat a hat\r\n"));
+        assert!(body.contains("name=\"n\"\r\n\r\n2\r\n"));
+        assert!(body.contains("name=\"response_format\"\r\n\r\nurl\r\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    multipart::{MultipartForm, MultipartRequest},
+    FileSource, ImageResponseFormat, ImageSize, Result,
+};
+
+/// Creates a variation of a given image. Only dall-e-2 is supported.
+#[derive(Debug)]
+pub struct CreateImageVariationRequest {
+    /// The image to use as the basis for the variation(s). Must be a valid PNG file, less than 4MB, and square.
+    pub image: FileSource,
+    /// The number of images to generate. Must be between 1 and 10.
+    pub n: Option<usize>,
+    /// The format in which the generated images are returned. Must be one of url or b64_json.
+    pub response_format: Option<ImageResponseFormat>,
+    /// The size of the generated images. Must be one of 256x256, 512x512, or 1024x1024.
+    pub size: Option<ImageSize>,
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
+    pub user: Option<String>,
+}
+
+impl CreateImageVariationRequest {
+    pub fn new(image: impl Into<FileSource>) -> Self {
+        CreateImageVariationRequest {
+            image: image.into(),
+            n: None,
+            response_format: None,
+            size: None,
+            user: None,
+        }
+    }
+
+    pub(crate) async fn into_multipart(self) -> Result<MultipartRequest> {
+        let form = MultipartForm::new()
+            .file_source("image", self.image)
+            .await?
+            .optional("n", self.n)
+            .optional("response_format", self.response_format)
+            .optional("size", self.size)
+            .optional("user", self.user);
+        Ok(MultipartRequest::new("images/variations", form))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_create_image_variation_from_path() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(json!({
+            "created": 1,
+            "data": [{ "b64_json": "UE5H" }]
+        }))])
+        .await;
+        let path = std::env::temp_dir().join("llm-sdk-variation-test.png");
+        tokio::fs::write(&path, b"PNG").await?;
+
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = CreateImageVariationRequest {
+            size: Some(ImageSize::Large),
+            ..CreateImageVariationRequest::new(path.as_path())
+        };
+        let res = sdk.create_image_variation(req).await?;
+        assert_eq!(res.data[0].b64_json.as_deref(), Some("UE5H"));
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/images/variations");
+        let body = String::from_utf8_lossy(&req.body);
+        assert!(body.contains("filename=\"llm-sdk-variation-test.png\"\r\nContent-Type: image/png\r\n\r\nPNG\r\n"));
+        assert!(body.contains("name=\"size\"\r\n\r\n1024x1024\r\n"));
+        tokio::fs::remove_file(&path).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_image_variation_missing_file() -> Result<()> {
+        let sdk = LLMSDK::new("token".into());
+        let req = CreateImageVariationRequest::new("/nonexistent/llm-sdk.png");
+        let err = sdk.create_image_variation(req).await.unwrap_err();
+        assert!(matches!(err, crate::LlmError::Io(_)));
+        Ok(())
+    }
+}
@@ -0,0 +1,333 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{IntoRequest, LlmError, RequestContext, Result};
+
+/// Classifies if text and/or image inputs are potentially harmful.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateModerationRequest {
+    /// Input (or inputs) to classify: a string, an array of strings, or an array of text and image_url parts.
+    pub input: ModerationInput,
+    /// The content moderation model you would like to use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<ModerationModel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ModerationInput {
+    String(String),
+    StringArray(Vec<String>),
+    /// Text and images; only supported by the omni-moderation models.
+    MultiModal(Vec<ModerationContent>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModerationContent {
+    Text { text: String },
+    ImageUrl { image_url: ModerationImageUrl },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModerationImageUrl {
+    /// Either a URL of the image or the base64 encoded image data as a data URL.
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub enum ModerationModel {
+    #[default]
+    #[serde(rename = "omni-moderation-latest")]
+    OmniModerationLatest,
+    #[serde(rename = "text-moderation-latest")]
+    TextModerationLatest,
+    #[serde(rename = "text-moderation-stable")]
+    TextModerationStable,
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl CreateModerationRequest {
+    pub fn new(input: impl Into<ModerationInput>) -> Self {
+        CreateModerationRequest {
+            input: input.into(),
+            model: None,
+        }
+    }
+
+    /// Rejects images sent to a text-only moderation model.
+    pub fn validate(&self) -> Result<()> {
+        let has_image = matches!(&self.input, ModerationInput::MultiModal(parts)
+            if parts.iter().any(|part| matches!(part, ModerationContent::ImageUrl { .. })));
+        let text_only = matches!(
+            self.model,
+            Some(ModerationModel::TextModerationLatest | ModerationModel::TextModerationStable)
+        );
+        if has_image && text_only {
+            return Err(LlmError::Validation(
+                "image inputs are only supported by the omni-moderation models".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl IntoRequest for CreateModerationRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("moderations").json(&self)
+    }
+}
+
+impl ModerationContent {
+    pub fn text(text: impl Into<String>) -> Self {
+        ModerationContent::Text { text: text.into() }
+    }
+
+    pub fn image_url(url: impl Into<String>) -> Self {
+        ModerationContent::ImageUrl {
+            image_url: ModerationImageUrl { url: url.into() },
+        }
+    }
+}
+
+impl From<String> for ModerationInput {
+    fn from(s: String) -> Self {
+        ModerationInput::String(s)
+    }
+}
+
+impl From<&str> for ModerationInput {
+    fn from(s: &str) -> Self {
+        ModerationInput::String(s.into())
+    }
+}
+
+impl From<Vec<String>> for ModerationInput {
+    fn from(s: Vec<String>) -> Self {
+        ModerationInput::StringArray(s)
+    }
+}
+
+impl From<Vec<ModerationContent>> for ModerationInput {
+    fn from(parts: Vec<ModerationContent>) -> Self {
+        ModerationInput::MultiModal(parts)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateModerationResponse {
+    /// The unique identifier for the moderation request.
+    pub id: String,
+    /// The model used to generate the moderation results.
+    pub model: String,
+    /// A list of moderation objects, one per input.
+    pub results: Vec<ModerationResult>,
+}
+
+impl CreateModerationResponse {
+    /// Whether any of the inputs was flagged.
+    pub fn flagged(&self) -> bool {
+        self.results.iter().any(|result| result.flagged)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModerationResult {
+    /// Whether any of the categories are flagged.
+    pub flagged: bool,
+    /// The categories and whether they are flagged.
+    pub categories: ModerationCategories<bool>,
+    /// The categories and their scores as predicted by the model.
+    pub category_scores: ModerationCategories<f64>,
+    /// The input types (text and/or image) each category score applies to. Only returned by the omni-moderation models.
+    #[serde(default)]
+    pub category_applied_input_types: Option<ModerationCategories<Vec<ModerationInputType>>>,
+}
+
+impl ModerationResult {
+    /// The names of the flagged categories, e.g. `violence/graphic`.
+    pub fn flagged_categories(&self) -> Vec<&'static str> {
+        self.categories
+            .iter()
+            .filter(|(_, flagged)| **flagged)
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationInputType {
+    Text,
+    Image,
+    /// An input type this SDK does not know yet.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A value for every moderation category: whether it is flagged, its score, or the input types
+/// it applies to. Categories a model does not support are left at their default.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de> + Default"))]
+pub struct ModerationCategories<T> {
+    #[serde(default, deserialize_with = "null_default")]
+    pub harassment: T,
+    #[serde(default, rename = "harassment/threatening", deserialize_with = "null_default")]
+    pub harassment_threatening: T,
+    #[serde(default, deserialize_with = "null_default")]
+    pub hate: T,
+    #[serde(default, rename = "hate/threatening", deserialize_with = "null_default")]
+    pub hate_threatening: T,
+    #[serde(default, deserialize_with = "null_default")]
+    pub illicit: T,
+    #[serde(default, rename = "illicit/violent", deserialize_with = "null_default")]
+    pub illicit_violent: T,
+    #[serde(default, rename = "self-harm", deserialize_with = "null_default")]
+    pub self_harm: T,
+    #[serde(default, rename = "self-harm/intent", deserialize_with = "null_default")]
+    pub self_harm_intent: T,
+    #[serde(default, rename = "self-harm/instructions", deserialize_with = "null_default")]
+    pub self_harm_instructions: T,
+    #[serde(default, deserialize_with = "null_default")]
+    pub sexual: T,
+    #[serde(default, rename = "sexual/minors", deserialize_with = "null_default")]
+    pub sexual_minors: T,
+    #[serde(default, deserialize_with = "null_default")]
+    pub violence: T,
+    #[serde(default, rename = "violence/graphic", deserialize_with = "null_default")]
+    pub violence_graphic: T,
+}
+
+impl<T> ModerationCategories<T> {
+    /// Every category with its API name.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &T)> {
+        [
+            ("harassment", &self.harassment),
+            ("harassment/threatening", &self.harassment_threatening),
+            ("hate", &self.hate),
+            ("hate/threatening", &self.hate_threatening),
+            ("illicit", &self.illicit),
+            ("illicit/violent", &self.illicit_violent),
+            ("self-harm", &self.self_harm),
+            ("self-harm/intent", &self.self_harm_intent),
+            ("self-harm/instructions", &self.self_harm_instructions),
+            ("sexual", &self.sexual),
+            ("sexual/minors", &self.sexual_minors),
+            ("violence", &self.violence),
+            ("violence/graphic", &self.violence_graphic),
+        ]
+        .into_iter()
+    }
+}
+
+fn null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[test]
+    fn test_moderation_request_serialize() -> Result<()> {
+        let req = CreateModerationRequest::new(vec!["hello".to_string(), "world".to_string()]);
+        assert_eq!(serde_json::to_value(&req)?, json!({ "input": ["hello", "world"] }));
+
+        let req = CreateModerationRequest {
+            model: Some(ModerationModel::OmniModerationLatest),
+            ..CreateModerationRequest::new(vec![
+                ModerationContent::text("draw this"),
+                ModerationContent::image_url("https://example.com/cat.png"),
+            ])
+        };
+        assert_eq!(
+            serde_json::to_value(&req)?,
+            json!({
+                "input": [
+                    { "type": "text", "text": "draw this" },
+                    { "type": "image_url", "image_url": { "url": "https://example.com/cat.png" } }
+                ],
+                "model": "omni-moderation-latest"
+            })
+        );
+        assert!(req.validate().is_ok());
+        let req = CreateModerationRequest {
+            model: Some(ModerationModel::TextModerationLatest),
+            ..req
+        };
+        assert!(matches!(req.validate(), Err(LlmError::Validation(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_moderation() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(json!({
+            "id": "modr-1",
+            "model": "omni-moderation-latest",
+            "results": [{
+                "flagged": true,
+                "categories": {
+                    "harassment": false, "harassment/threatening": false, "hate": false, "hate/threatening": false,
+                    "illicit": false, "illicit/violent": false, "self-harm": false, "self-harm/intent": false,
+                    "self-harm/instructions": false, "sexual": false, "sexual/minors": false,
+                    "violence": true, "violence/graphic": true
+                },
+                "category_scores": {
+                    "harassment": 0.0001, "harassment/threatening": 0.0001, "hate": 0.00002, "hate/threatening": 0.000001,
+                    "illicit": 0.00001, "illicit/violent": 0.00001, "self-harm": 0.0002, "self-harm/intent": 0.0001,
+                    "self-harm/instructions": 0.0001, "sexual": 0.00003, "sexual/minors": 0.000002,
+                    "violence": 0.89, "violence/graphic": 0.86
+                },
+                "category_applied_input_types": {
+                    "harassment": ["text"], "harassment/threatening": ["text"], "hate": ["text"], "hate/threatening": ["text"],
+                    "illicit": ["text"], "illicit/violent": ["text"], "self-harm": ["text", "image"],
+                    "self-harm/intent": ["text", "image"], "self-harm/instructions": ["text", "image"],
+                    "sexual": ["text", "image"], "sexual/minors": ["text"],
+                    "violence": ["text", "image", "audio"], "violence/graphic": ["text", "image"]
+                }
+            }]
+        }))])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let res = sdk
+            .create_moderation(CreateModerationRequest::new(vec![
+                ModerationContent::text("a violent scene"),
+                ModerationContent::image_url("data:image/png;base64,UE5H"),
+            ]))
+            .await?;
+        assert!(res.flagged());
+        let result = &res.results[0];
+        assert_eq!(result.flagged_categories(), ["violence", "violence/graphic"]);
+        assert_eq!(result.category_scores.violence, 0.89);
+        let applied = result.category_applied_input_types.as_ref().unwrap();
+        assert_eq!(applied.violence_graphic, [ModerationInputType::Text, ModerationInputType::Image]);
+        assert_eq!(applied.violence[2], ModerationInputType::Unknown);
+        assert_eq!(server.requests()[0].path, "/moderations");
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_moderation_null_categories() -> Result<()> {
+        let result: ModerationResult = serde_json::from_value(json!({
+            "flagged": false,
+            "categories": { "hate": false, "illicit": null },
+            "category_scores": { "hate": 0.01, "illicit": null }
+        }))?;
+        assert!(!result.categories.illicit);
+        assert_eq!(result.category_scores.hate, 0.01);
+        assert!(result.category_applied_input_types.is_none());
+        Ok(())
+    }
+}
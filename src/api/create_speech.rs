@@ -0,0 +1,198 @@
+use reqwest::RequestBuilder;
+use serde::Serialize;
+
+use crate::{IntoRequest, LlmError, RequestContext, Result};
+
+/// Generates audio from the input text.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateSpeechRequest {
+    /// One of the available TTS models: tts-1, tts-1-hd or gpt-4o-mini-tts.
+    pub model: SpeechModel,
+    /// The text to generate audio for. The maximum length is 4096 characters.
+    pub input: String,
+    /// The voice to use when generating the audio.
+    pub voice: SpeechVoice,
+    /// Control the voice of your generated audio with additional instructions. Does not work with tts-1 or tts-1-hd.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// The format to audio in. Supported formats are mp3, opus, aac, flac, wav, and pcm.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<SpeechResponseFormat>,
+    /// The speed of the generated audio. Select a value from 0.25 to 4.0. 1.0 is the default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub enum SpeechModel {
+    #[default]
+    #[serde(rename = "tts-1")]
+    Tts1,
+    #[serde(rename = "tts-1-hd")]
+    Tts1Hd,
+    #[serde(rename = "gpt-4o-mini-tts")]
+    Gpt4oMiniTts,
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeechVoice {
+    #[default]
+    Alloy,
+    Ash,
+    Ballad,
+    Coral,
+    Echo,
+    Fable,
+    Onyx,
+    Nova,
+    Sage,
+    Shimmer,
+    Verse,
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeechResponseFormat {
+    #[default]
+    Mp3,
+    Opus,
+    Aac,
+    Flac,
+    Wav,
+    /// Raw samples in 24kHz (16-bit signed, little-endian), without the header.
+    Pcm,
+}
+
+impl CreateSpeechRequest {
+    pub fn new(input: impl Into<String>, voice: SpeechVoice) -> Self {
+        CreateSpeechRequest {
+            model: SpeechModel::default(),
+            input: input.into(),
+            voice,
+            instructions: None,
+            response_format: None,
+            speed: None,
+        }
+    }
+
+    /// Checks the limits of the endpoint, so an invalid request fails before anything is sent.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: String| Err(LlmError::Validation(msg));
+        let len = self.input.chars().count();
+        if self.input.trim().is_empty() {
+            return invalid("input must not be empty".into());
+        }
+        if len > 4096 {
+            return invalid(format!("input is {len} characters, at most 4096 are allowed"));
+        }
+        if let Some(speed) = self.speed {
+            if !(0.25..=4.0).contains(&speed) {
+                return invalid(format!("speed must be between 0.25 and 4.0, got {speed}"));
+            }
+        }
+        if self.instructions.is_some() && matches!(self.model, SpeechModel::Tts1 | SpeechModel::Tts1Hd) {
+            return invalid("instructions are not supported by tts-1 and tts-1-hd".into());
+        }
+        Ok(())
+    }
+}
+
+impl IntoRequest for CreateSpeechRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("audio/speech").json(&self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use futures_util::StreamExt;
+    use serde_json::json;
+
+    #[test]
+    fn test_speech_request_validate() {
+        let ok = |req: CreateSpeechRequest| req.validate().is_ok();
+        assert!(ok(CreateSpeechRequest::new("Hello", SpeechVoice::Nova)));
+        assert!(!ok(CreateSpeechRequest::new("", SpeechVoice::Nova)));
+        assert!(!ok(CreateSpeechRequest::new("a".repeat(4097), SpeechVoice::Nova)));
+        assert!(!ok(CreateSpeechRequest {
+            speed: Some(5.0),
+            ..CreateSpeechRequest::new("Hello", SpeechVoice::Nova)
+        }));
+        assert!(!ok(CreateSpeechRequest {
+            instructions: Some("Speak calmly".into()),
+            ..CreateSpeechRequest::new("Hello", SpeechVoice::Nova)
+        }));
+        assert!(ok(CreateSpeechRequest {
+            model: SpeechModel::Gpt4oMiniTts,
+            instructions: Some("Speak calmly".into()),
+            ..CreateSpeechRequest::new("Hello", SpeechVoice::Nova)
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_create_speech_streams_to_writer() -> Result<()> {
+        let audio: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let server =
+            MockServer::start(vec![MockResponse::new(200, audio.clone()).header("content-type", "audio/wav")]).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = CreateSpeechRequest {
+            model: SpeechModel::Gpt4oMiniTts,
+            instructions: Some("Speak like a narrator".into()),
+            response_format: Some(SpeechResponseFormat::Wav),
+            speed: Some(1.25),
+            ..CreateSpeechRequest::new("Once upon a time", SpeechVoice::Coral)
+        };
+        let stream = sdk.create_speech(req).await?;
+        assert_eq!(stream.content_type(), Some("audio/wav"));
+        let mut out = vec![];
+        let written = stream.write_to(&mut out).await?;
+        assert_eq!(written, audio.len() as u64);
+        assert_eq!(out, audio);
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/audio/speech");
+        assert_eq!(
+            req.json(),
+            json!({
+                "model": "gpt-4o-mini-tts",
+                "input": "Once upon a time",
+                "voice": "coral",
+                "instructions": "Speak like a narrator",
+                "response_format": "wav",
+                "speed": 1.25,
+            })
+        );
+
+        let mut stream = sdk.create_speech(CreateSpeechRequest::new("Hi", SpeechVoice::Alloy)).await?;
+        let mut len = 0;
+        while let Some(chunk) = stream.next().await {
+            len += chunk?.len();
+        }
+        assert_eq!(len, audio.len());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_create_speech_live() -> Result<()> {
+        let sdk = LLMSDK::new(std::env::var("OPENAI_API_KEY")?);
+        let req = CreateSpeechRequest::new("Life is short, play more!", SpeechVoice::Alloy);
+        let path = std::env::temp_dir().join("llm-sdk-speech.mp3");
+        let mut file = tokio::fs::File::create(&path).await?;
+        let written = sdk.create_speech(req).await?.write_to(&mut file).await?;
+        assert!(written > 0);
+        Ok(())
+    }
+}
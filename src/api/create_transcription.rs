@@ -0,0 +1,330 @@
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    multipart::{MultipartForm, MultipartRequest},
+    FileSource, LlmError, Result,
+};
+
+/// Transcribes audio into the input language.
+#[derive(Debug)]
+pub struct CreateTranscriptionRequest {
+    /// The audio file to transcribe, in one of these formats: flac, mp3, mp4, mpeg, mpga, m4a, ogg, wav, or webm.
+    pub file: FileSource,
+    /// ID of the model to use.
+    pub model: TranscriptionModel,
+    /// The language of the input audio in ISO-639-1 format, e.g. `en`. Supplying it improves accuracy and latency.
+    pub language: Option<String>,
+    /// An optional text to guide the model's style or continue a previous audio segment. The prompt should match the audio language.
+    pub prompt: Option<String>,
+    /// The format of the output. gpt-4o-transcribe and gpt-4o-mini-transcribe only support json and text.
+    pub response_format: Option<TranscriptionResponseFormat>,
+    /// The sampling temperature, between 0 and 1.
+    pub temperature: Option<f32>,
+    /// The timestamp granularities to populate for this transcription. response_format must be set to verbose_json.
+    pub timestamp_granularities: Vec<TimestampGranularity>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub enum TranscriptionModel {
+    #[default]
+    #[serde(rename = "whisper-1")]
+    Whisper1,
+    #[serde(rename = "gpt-4o-transcribe")]
+    Gpt4oTranscribe,
+    #[serde(rename = "gpt-4o-mini-transcribe")]
+    Gpt4oMiniTranscribe,
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionResponseFormat {
+    #[default]
+    Json,
+    Text,
+    Srt,
+    Vtt,
+    VerboseJson,
+}
+
+impl TranscriptionResponseFormat {
+    /// Whether the API answers with a json object rather than plain text.
+    pub fn is_json(&self) -> bool {
+        matches!(self, TranscriptionResponseFormat::Json | TranscriptionResponseFormat::VerboseJson)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampGranularity {
+    Word,
+    Segment,
+}
+
+impl CreateTranscriptionRequest {
+    pub fn new(file: impl Into<FileSource>) -> Self {
+        CreateTranscriptionRequest {
+            file: file.into(),
+            model: TranscriptionModel::default(),
+            language: None,
+            prompt: None,
+            response_format: None,
+            temperature: None,
+            timestamp_granularities: vec![],
+        }
+    }
+
+    pub(crate) async fn into_multipart(self) -> Result<MultipartRequest> {
+        let format = self.response_format.unwrap_or_default();
+        if !self.timestamp_granularities.is_empty() && format != TranscriptionResponseFormat::VerboseJson {
+            return Err(LlmError::Validation(
+                "timestamp_granularities require the verbose_json response format".into(),
+            ));
+        }
+        if matches!(
+            self.model,
+            TranscriptionModel::Gpt4oTranscribe | TranscriptionModel::Gpt4oMiniTranscribe
+        ) && !matches!(format, TranscriptionResponseFormat::Json | TranscriptionResponseFormat::Text)
+        {
+            return Err(LlmError::Validation(format!(
+                "{format:?} is not supported by the gpt-4o transcribe models, use json or text"
+            )));
+        }
+        let mut form = MultipartForm::new()
+            .file_source("file", self.file)
+            .await?
+            .optional("model", Some(self.model))
+            .optional("language", self.language)
+            .optional("prompt", self.prompt)
+            .optional("response_format", self.response_format);
+        if let Some(temperature) = self.temperature {
+            form = form.text("temperature", temperature);
+        }
+        for granularity in self.timestamp_granularities {
+            form = form.optional("timestamp_granularities[]", Some(granularity));
+        }
+        Ok(MultipartRequest::new("audio/transcriptions", form))
+    }
+}
+
+/// The transcribed text. `language`, `duration`, `segments` and `words` are only filled for the
+/// verbose_json response format; for text, srt and vtt, `text` holds the body as returned.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct CreateTranscriptionResponse {
+    /// The transcribed text.
+    pub text: String,
+    /// The language of the input audio.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// The duration of the input audio in seconds.
+    #[serde(default)]
+    pub duration: Option<f64>,
+    /// Segments of the transcribed text and their corresponding details.
+    #[serde(default)]
+    pub segments: Vec<TranscriptionSegment>,
+    /// Extracted words and their corresponding timestamps.
+    #[serde(default)]
+    pub words: Vec<TranscriptionWord>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct TranscriptionSegment {
+    /// Unique identifier of the segment.
+    pub id: usize,
+    /// Seek offset of the segment.
+    #[serde(default)]
+    pub seek: usize,
+    /// Start time of the segment in seconds.
+    pub start: f64,
+    /// End time of the segment in seconds.
+    pub end: f64,
+    /// Text content of the segment.
+    pub text: String,
+    /// Array of token IDs for the text content.
+    #[serde(default)]
+    pub tokens: Vec<u32>,
+    /// Temperature parameter used for generating the segment.
+    #[serde(default)]
+    pub temperature: f64,
+    /// Average logprob of the segment. If the value is lower than -1, consider the logprobs failed.
+    #[serde(default)]
+    pub avg_logprob: f64,
+    /// Compression ratio of the segment. If the value is greater than 2.4, consider the compression failed.
+    #[serde(default)]
+    pub compression_ratio: f64,
+    /// Probability of no speech in the segment. If the value is higher than 1.0 and the avg_logprob is below -1, consider this segment silent.
+    #[serde(default)]
+    pub no_speech_prob: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct TranscriptionWord {
+    /// The text content of the word.
+    pub word: String,
+    /// Start time of the word in seconds.
+    pub start: f64,
+    /// End time of the word in seconds.
+    pub end: f64,
+}
+
+impl CreateTranscriptionResponse {
+    /// Wraps a text, srt or vtt body.
+    pub(crate) fn from_text(text: String) -> Self {
+        CreateTranscriptionResponse {
+            text,
+            ..Default::default()
+        }
+    }
+
+    /// Renders the segments as a SubRip (.srt) subtitle file. Requires the verbose_json response format.
+    pub fn to_srt(&self) -> String {
+        let mut out = String::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            let _ = write!(
+                out,
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                format_timestamp(segment.start, ','),
+                format_timestamp(segment.end, ','),
+                segment.text.trim()
+            );
+        }
+        out
+    }
+
+    /// Renders the segments as a WebVTT (.vtt) subtitle file. Requires the verbose_json response format.
+    pub fn to_vtt(&self) -> String {
+        let mut out = String::from("WEBVTT\n\n");
+        for segment in &self.segments {
+            let _ = write!(
+                out,
+                "{} --> {}\n{}\n\n",
+                format_timestamp(segment.start, '.'),
+                format_timestamp(segment.end, '.'),
+                segment.text.trim()
+            );
+        }
+        out
+    }
+}
+
+/// Formats seconds as `hh:mm:ss` followed by the separator and milliseconds.
+fn format_timestamp(seconds: f64, separator: char) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn verbose_response() -> serde_json::Value {
+        json!({
+            "task": "transcribe",
+            "language": "english",
+            "duration": 3725.5,
+            "text": "Hello there. General Kenobi.",
+            "segments": [
+                { "id": 0, "seek": 0, "start": 0.0, "end": 1.52, "text": " Hello there.", "tokens": [50364, 2425], "temperature": 0.0, "avg_logprob": -0.2, "compression_ratio": 0.8, "no_speech_prob": 0.01 },
+                { "id": 1, "seek": 0, "start": 3723.0, "end": 3725.5, "text": " General Kenobi.", "tokens": [50440], "temperature": 0.0, "avg_logprob": -0.3, "compression_ratio": 0.8, "no_speech_prob": 0.02 }
+            ],
+            "words": [
+                { "word": "Hello", "start": 0.0, "end": 0.6 },
+                { "word": "there", "start": 0.6, "end": 1.52 }
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_transcription_verbose() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(verbose_response())]).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = CreateTranscriptionRequest {
+            language: Some("en".into()),
+            temperature: Some(0.2),
+            response_format: Some(TranscriptionResponseFormat::VerboseJson),
+            timestamp_granularities: vec![TimestampGranularity::Word, TimestampGranularity::Segment],
+            ..CreateTranscriptionRequest::new(FileSource::bytes("talk.mp3", &b"MP3"[..]))
+        };
+        let res = sdk.create_transcription(req).await?;
+        assert_eq!(res.language.as_deref(), Some("english"));
+        assert_eq!(res.segments.len(), 2);
+        assert_eq!(res.words[1].word, "there");
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/audio/transcriptions");
+        let body = String::from_utf8_lossy(&req.body);
+        assert!(body.contains("name=\"file\"; filename=\"talk.mp3\"\r\nContent-Type: audio/mpeg\r\n\r\nMP3\r\n"));
+        assert!(body.contains("name=\"model\"\r\n\r\nwhisper-1\r\n"));
+        assert!(body.contains("name=\"language\"\r\n\r\nen\r\n"));
+        assert!(body.contains("name=\"temperature\"\r\n\r\n0.2\r\n"));
+        assert!(body.contains("name=\"response_format\"\r\n\r\nverbose_json\r\n"));
+        assert_eq!(body.matches("name=\"timestamp_granularities[]\"").count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_transcription_text_formats() -> Result<()> {
+        let srt = "1\n00:00:00,000 --> 00:00:01,520\nHello there.\n\n";
+        let server = MockServer::start(vec![MockResponse::new(200, srt).header("content-type", "text/plain")]).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = CreateTranscriptionRequest {
+            response_format: Some(TranscriptionResponseFormat::Srt),
+            ..CreateTranscriptionRequest::new(FileSource::bytes("talk.mp3", &b"MP3"[..]))
+        };
+        let res = sdk.create_transcription(req).await?;
+        assert_eq!(res.text, srt);
+        assert!(res.segments.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_transcription_validation() -> Result<()> {
+        let file = || FileSource::bytes("talk.mp3", &b"MP3"[..]);
+        let req = CreateTranscriptionRequest {
+            timestamp_granularities: vec![TimestampGranularity::Word],
+            ..CreateTranscriptionRequest::new(file())
+        };
+        assert!(matches!(req.into_multipart().await, Err(LlmError::Validation(_))));
+        let req = CreateTranscriptionRequest {
+            model: TranscriptionModel::Gpt4oTranscribe,
+            response_format: Some(TranscriptionResponseFormat::Srt),
+            ..CreateTranscriptionRequest::new(file())
+        };
+        assert!(matches!(req.into_multipart().await, Err(LlmError::Validation(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_transcription_subtitles() -> Result<()> {
+        let res: CreateTranscriptionResponse = serde_json::from_value(verbose_response())?;
+        assert_eq!(
+            res.to_srt(),
+            "1\n00:00:00,000 --> 00:00:01,520\nHello there.\n\n\
+             2\n01:02:03,000 --> 01:02:05,500\nGeneral Kenobi.\n\n"
+        );
+        assert_eq!(
+            res.to_vtt(),
+            "WEBVTT\n\n\
+             00:00:00.000 --> 00:00:01.520\nHello there.\n\n\
+             01:02:03.000 --> 01:02:05.500\nGeneral Kenobi.\n\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    multipart::{MultipartForm, MultipartRequest},
+    CreateTranscriptionResponse, FileSource, Result, TranscriptionModel, TranscriptionResponseFormat,
+};
+
+/// Translates audio into English.
+#[derive(Debug)]
+pub struct CreateTranslationRequest {
+    /// The audio file to translate, in one of these formats: flac, mp3, mp4, mpeg, mpga, m4a, ogg, wav, or webm.
+    pub file: FileSource,
+    /// ID of the model to use. Only whisper-1 is currently available.
+    pub model: TranscriptionModel,
+    /// An optional text to guide the model's style or continue a previous audio segment. The prompt should be in English.
+    pub prompt: Option<String>,
+    /// The format of the output, in one of these options: json, text, srt, verbose_json, or vtt.
+    pub response_format: Option<TranscriptionResponseFormat>,
+    /// The sampling temperature, between 0 and 1.
+    pub temperature: Option<f32>,
+}
+
+/// The English text. Segments are filled for the verbose_json response format; for text, srt
+/// and vtt, `text` holds the body as returned.
+pub type CreateTranslationResponse = CreateTranscriptionResponse;
+
+impl CreateTranslationRequest {
+    pub fn new(file: impl Into<FileSource>) -> Self {
+        CreateTranslationRequest {
+            file: file.into(),
+            model: TranscriptionModel::default(),
+            prompt: None,
+            response_format: None,
+            temperature: None,
+        }
+    }
+
+    pub(crate) async fn into_multipart(self) -> Result<MultipartRequest> {
+        let mut form = MultipartForm::new()
+            .file_source("file", self.file)
+            .await?
+            .optional("model", Some(self.model))
+            .optional("prompt", self.prompt)
+            .optional("response_format", self.response_format);
+        if let Some(temperature) = self.temperature {
+            form = form.text("temperature", temperature);
+        }
+        Ok(MultipartRequest::new("audio/translations", form))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_create_translation() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::json(json!({
+                "task": "translate",
+                "language": "german",
+                "duration": 2.5,
+                "text": "Please call me back.",
+                "segments": [{ "id": 0, "seek": 0, "start": 0.0, "end": 2.5, "text": " Please call me back.", "tokens": [], "temperature": 0.0, "avg_logprob": -0.2, "compression_ratio": 0.7, "no_speech_prob": 0.0 }]
+            })),
+            MockResponse::new(200, "Please call me back.\n").header("content-type", "text/plain"),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = CreateTranslationRequest {
+            prompt: Some("Voicemail.".into()),
+            response_format: Some(TranscriptionResponseFormat::VerboseJson),
+            ..CreateTranslationRequest::new(FileSource::bytes("voicemail.ogg", &b"OGG"[..]))
+        };
+        let res = sdk.create_translation(req).await?;
+        assert_eq!(res.language.as_deref(), Some("german"));
+        assert_eq!(res.to_srt(), "1\n00:00:00,000 --> 00:00:02,500\nPlease call me back.\n\n");
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/audio/translations");
+        let body = String::from_utf8_lossy(&req.body);
+        assert!(body.contains("name=\"file\"; filename=\"voicemail.ogg\"\r\nContent-Type: audio/ogg\r\n\r\nOGG\r\n"));
+        assert!(body.contains("name=\"model\"\r\n\r\nwhisper-1\r\n"));
+        assert!(body.contains("name=\"prompt\"\r\n\r\nVoicemail.\r\n"));
+
+        let req = CreateTranslationRequest {
+            response_format: Some(TranscriptionResponseFormat::Text),
+            ..CreateTranslationRequest::new(FileSource::bytes("voicemail.ogg", &b"OGG"[..]))
+        };
+        let res = sdk.create_translation(req).await?;
+        assert_eq!(res.text, "Please call me back.\n");
+        Ok(())
+    }
+}
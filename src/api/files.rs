@@ -0,0 +1,268 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    encode_path_segment,
+    multipart::{MultipartForm, MultipartRequest},
+    FileSource, IntoRequest, ListOrder, ListResponse, RequestContext, Result,
+};
+
+/// Uploads a file that can be used across various endpoints. Individual files can be up to 512 MB.
+#[derive(Debug)]
+pub struct CreateFileRequest {
+    /// The file to upload. Paths and readers are streamed, not loaded into memory.
+    pub file: FileSource,
+    /// The intended purpose of the uploaded file.
+    pub purpose: FilePurpose,
+}
+
+/// What a file is used for. The `*_output` and `*-results` purposes are only set by the API on
+/// the files it creates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilePurpose {
+    #[serde(rename = "fine-tune")]
+    FineTune,
+    #[serde(rename = "fine-tune-results")]
+    FineTuneResults,
+    #[serde(rename = "batch")]
+    Batch,
+    #[serde(rename = "batch_output")]
+    BatchOutput,
+    #[serde(rename = "assistants")]
+    Assistants,
+    #[serde(rename = "assistants_output")]
+    AssistantsOutput,
+    #[serde(rename = "vision")]
+    Vision,
+    #[serde(rename = "user_data")]
+    UserData,
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl CreateFileRequest {
+    pub fn new(file: impl Into<FileSource>, purpose: FilePurpose) -> Self {
+        CreateFileRequest {
+            file: file.into(),
+            purpose,
+        }
+    }
+
+    pub(crate) async fn into_multipart(self) -> Result<MultipartRequest> {
+        let form = MultipartForm::new()
+            .optional("purpose", Some(self.purpose))
+            .file_stream("file", self.file)
+            .await?;
+        Ok(MultipartRequest::new("files", form))
+    }
+}
+
+/// Returns a list of files, one page at a time.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListFilesRequest {
+    /// A cursor for use in pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// A limit on the number of objects to be returned. Limit can range between 1 and 10,000, and the default is 10,000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Sort order by the created_at timestamp of the objects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<ListOrder>,
+    /// Only return files with the given purpose.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<FilePurpose>,
+}
+
+impl IntoRequest for ListFilesRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get("files").query(&self)
+    }
+}
+
+/// Returns information about a specific file.
+#[derive(Debug, Clone)]
+pub struct RetrieveFileRequest {
+    pub id: String,
+}
+
+impl IntoRequest for RetrieveFileRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("files/{}", encode_path_segment(&self.id)))
+    }
+}
+
+/// Deletes a file.
+#[derive(Debug, Clone)]
+pub struct DeleteFileRequest {
+    pub id: String,
+}
+
+impl IntoRequest for DeleteFileRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.delete(&format!("files/{}", encode_path_segment(&self.id)))
+    }
+}
+
+/// Returns the contents of the specified file.
+#[derive(Debug, Clone)]
+pub struct FileContentRequest {
+    pub id: String,
+}
+
+impl IntoRequest for FileContentRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("files/{}/content", encode_path_segment(&self.id)))
+    }
+}
+
+/// A document that has been uploaded to OpenAI.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct FileObject {
+    /// The file identifier, which can be referenced in the API endpoints.
+    pub id: String,
+    /// The object type, which is always "file".
+    pub object: String,
+    /// The size of the file, in bytes.
+    pub bytes: u64,
+    /// The Unix timestamp (in seconds) for when the file was created.
+    pub created_at: u64,
+    /// The Unix timestamp (in seconds) for when the file will expire.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// The name of the file.
+    pub filename: String,
+    /// The intended purpose of the file.
+    pub purpose: FilePurpose,
+}
+
+pub type ListFilesResponse = ListResponse<FileObject>;
+
+impl ListFilesResponse {
+    /// The request for the page after this one, if there is one.
+    pub fn next_page(&self, req: &ListFilesRequest) -> Option<ListFilesRequest> {
+        let after = self.next_cursor(|file| &file.id)?;
+        Some(ListFilesRequest {
+            after: Some(after.to_string()),
+            ..req.clone()
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DeleteFileResponse {
+    pub id: String,
+    /// The object type, which is always "file".
+    pub object: String,
+    pub deleted: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LlmError, LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn file_object(id: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "object": "file",
+            "bytes": 120000,
+            "created_at": 1677610602,
+            "filename": "train.jsonl",
+            "purpose": "fine-tune"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_file_streams_reader() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(file_object("file-abc123"))]).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let lines: String = (0..20_000).map(|i| format!("{{\"line\":{i}}}\n")).collect();
+        let reader = std::io::Cursor::new(lines.clone().into_bytes());
+        let req = CreateFileRequest::new(FileSource::reader("train.jsonl", reader), FilePurpose::FineTune);
+        let file = sdk.create_file(req).await?;
+        assert_eq!(file.id, "file-abc123");
+        assert_eq!(file.purpose, FilePurpose::FineTune);
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/files");
+        assert_eq!(req.header("transfer-encoding"), Some("chunked"));
+        let body = String::from_utf8_lossy(&req.body);
+        assert!(body.contains("name=\"purpose\"\r\n\r\nfine-tune\r\n"));
+        let file_part = format!(
+            "name=\"file\"; filename=\"train.jsonl\"\r\nContent-Type: application/jsonl\r\n\r\n{lines}\r\n--"
+        );
+        assert!(body.contains(&file_part));
+        assert!(body.ends_with("--\r\n"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_file_from_path() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(file_object("file-abc123"))]).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let path = std::env::temp_dir().join(format!("llm-sdk-upload-{}.jsonl", std::process::id()));
+        tokio::fs::write(&path, "{\"custom_id\":\"1\"}\n").await?;
+        sdk.create_file(CreateFileRequest::new(path.as_path(), FilePurpose::Batch)).await?;
+        tokio::fs::remove_file(&path).await?;
+
+        let req = &server.requests()[0];
+        let len: usize = req.header("content-length").unwrap().parse()?;
+        assert_eq!(len, req.body.len());
+        assert!(String::from_utf8_lossy(&req.body).contains("\r\n\r\n{\"custom_id\":\"1\"}\n\r\n"));
+
+        let missing = CreateFileRequest::new("/no/such/file.jsonl", FilePurpose::Batch);
+        assert!(matches!(sdk.create_file(missing).await, Err(LlmError::Io(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_api() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::json(json!({
+                "object": "list",
+                "data": [file_object("file-1"), file_object("file-2")],
+                "first_id": "file-1",
+                "last_id": "file-2",
+                "has_more": true
+            })),
+            MockResponse::json(json!({ "object": "list", "data": [file_object("file-3")], "has_more": false })),
+            MockResponse::json(file_object("file-1")),
+            MockResponse::new(200, "{\"line\":1}\n").header("content-type", "application/octet-stream"),
+            MockResponse::json(json!({ "id": "file-1", "object": "file", "deleted": true })),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+
+        let req = ListFilesRequest {
+            limit: Some(2),
+            purpose: Some(FilePurpose::FineTune),
+            ..Default::default()
+        };
+        let files = sdk.list_all_files(req).await?;
+        let ids: Vec<_> = files.iter().map(|file| file.id.as_str()).collect();
+        assert_eq!(ids, ["file-1", "file-2", "file-3"]);
+        assert_eq!(sdk.retrieve_file("file-1").await?.bytes, 120000);
+        let content = sdk.file_content("file-1").await?.bytes().await?;
+        assert_eq!(&content[..], b"{\"line\":1}\n");
+        assert!(sdk.delete_file("file-1").await?.deleted);
+
+        assert_eq!(
+            server.calls(),
+            [
+                "GET /files?limit=2&purpose=fine-tune",
+                "GET /files?after=file-2&limit=2&purpose=fine-tune",
+                "GET /files/file-1",
+                "GET /files/file-1/content",
+                "DELETE /files/file-1",
+            ]
+        );
+        Ok(())
+    }
+}
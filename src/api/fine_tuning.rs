@@ -0,0 +1,674 @@
+use std::{collections::HashMap, time::Duration};
+
+use futures_util::{stream, stream::BoxStream, StreamExt};
+use reqwest::RequestBuilder;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::{encode_path_segment, IntoRequest, ListResponse, PollOptions, RequestContext, Result, LLMSDK};
+
+/// Creates a fine-tuning job which begins the process of creating a new model from a given dataset.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateFineTuningJobRequest {
+    /// The name of the model to fine-tune.
+    pub model: String,
+    /// The ID of an uploaded file that contains training data, uploaded with the fine-tune purpose.
+    pub training_file: String,
+    /// The ID of an uploaded file that contains validation data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_file: Option<String>,
+    /// The method used for fine-tuning, with its hyperparameters. Defaults to supervised.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<FineTuningMethod>,
+    /// A string of up to 64 characters that will be added to your fine-tuned model name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    /// The seed controls the reproducibility of the job.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    /// Set of 16 key-value pairs that can be attached to the job.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+impl CreateFineTuningJobRequest {
+    pub fn new(model: impl Into<String>, training_file: impl Into<String>) -> Self {
+        CreateFineTuningJobRequest {
+            model: model.into(),
+            training_file: training_file.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for CreateFineTuningJobRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("fine_tuning/jobs").json(&self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FineTuningMethod {
+    Supervised { supervised: SupervisedMethod },
+    /// Direct preference optimization.
+    Dpo { dpo: DpoMethod },
+}
+
+impl FineTuningMethod {
+    pub fn supervised(hyperparameters: Hyperparameters) -> Self {
+        FineTuningMethod::Supervised {
+            supervised: SupervisedMethod {
+                hyperparameters: Some(hyperparameters),
+            },
+        }
+    }
+
+    pub fn dpo(hyperparameters: DpoHyperparameters) -> Self {
+        FineTuningMethod::Dpo {
+            dpo: DpoMethod {
+                hyperparameters: Some(hyperparameters),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SupervisedMethod {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<Hyperparameters>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DpoMethod {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<DpoHyperparameters>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Hyperparameters {
+    /// Number of examples in each batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<Hyperparameter<u32>>,
+    /// Scaling factor for the learning rate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<Hyperparameter<f64>>,
+    /// The number of epochs to train the model for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<Hyperparameter<u32>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DpoHyperparameters {
+    /// The beta value for the DPO method. A higher beta value will increase the weight of the penalty between the policy and reference model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beta: Option<Hyperparameter<f64>>,
+    /// Number of examples in each batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<Hyperparameter<u32>>,
+    /// Scaling factor for the learning rate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<Hyperparameter<f64>>,
+    /// The number of epochs to train the model for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<Hyperparameter<u32>>,
+}
+
+/// A hyperparameter value, or `auto` to let the API pick one based on the dataset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hyperparameter<T> {
+    Auto,
+    Value(T),
+}
+
+impl<T: Serialize> Serialize for Hyperparameter<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Hyperparameter::Auto => serializer.serialize_str("auto"),
+            Hyperparameter::Value(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Hyperparameter<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr<T> {
+            Auto(String),
+            Value(T),
+        }
+        match Repr::deserialize(deserializer)? {
+            Repr::Auto(s) if s == "auto" => Ok(Hyperparameter::Auto),
+            Repr::Auto(s) => Err(D::Error::custom(format!("invalid hyperparameter value: {s}"))),
+            Repr::Value(value) => Ok(Hyperparameter::Value(value)),
+        }
+    }
+}
+
+/// Lists your organization's fine-tuning jobs.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListFineTuningJobsRequest {
+    /// Identifier for the last job from the previous pagination request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Number of fine-tuning jobs to retrieve.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl IntoRequest for ListFineTuningJobsRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get("fine_tuning/jobs").query(&self)
+    }
+}
+
+/// Gets info about a fine-tuning job.
+#[derive(Debug, Clone)]
+pub struct RetrieveFineTuningJobRequest {
+    pub id: String,
+}
+
+impl IntoRequest for RetrieveFineTuningJobRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("fine_tuning/jobs/{}", encode_path_segment(&self.id)))
+    }
+}
+
+/// Immediately cancels a fine-tuning job.
+#[derive(Debug, Clone)]
+pub struct CancelFineTuningJobRequest {
+    pub id: String,
+}
+
+impl IntoRequest for CancelFineTuningJobRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("fine_tuning/jobs/{}/cancel", encode_path_segment(&self.id)))
+    }
+}
+
+/// Gets status updates for a fine-tuning job, newest first.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListFineTuningEventsRequest {
+    #[serde(skip)]
+    pub id: String,
+    /// Identifier for the last event from the previous pagination request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Number of events to retrieve.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl ListFineTuningEventsRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        ListFineTuningEventsRequest {
+            id: id.into(),
+            after: None,
+            limit: None,
+        }
+    }
+}
+
+impl IntoRequest for ListFineTuningEventsRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("fine_tuning/jobs/{}/events", encode_path_segment(&self.id)))
+            .query(&self)
+    }
+}
+
+/// Lists checkpoints for a fine-tuning job.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListFineTuningCheckpointsRequest {
+    #[serde(skip)]
+    pub id: String,
+    /// Identifier for the last checkpoint ID from the previous pagination request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Number of checkpoints to retrieve.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl ListFineTuningCheckpointsRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        ListFineTuningCheckpointsRequest {
+            id: id.into(),
+            after: None,
+            limit: None,
+        }
+    }
+}
+
+impl IntoRequest for ListFineTuningCheckpointsRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("fine_tuning/jobs/{}/checkpoints", encode_path_segment(&self.id)))
+            .query(&self)
+    }
+}
+
+/// A fine-tuning job that has been created through the API.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FineTuningJob {
+    /// The object identifier, which can be referenced in the API endpoints.
+    pub id: String,
+    /// The object type, which is always "fine_tuning.job".
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the fine-tuning job was created.
+    pub created_at: u64,
+    /// For fine-tuning jobs that have failed, this will contain more information on the cause of the failure.
+    #[serde(default)]
+    pub error: Option<FineTuningJobError>,
+    /// The name of the fine-tuned model that is being created. Null while the job is still running.
+    #[serde(default)]
+    pub fine_tuned_model: Option<String>,
+    /// The Unix timestamp (in seconds) for when the fine-tuning job was finished.
+    #[serde(default)]
+    pub finished_at: Option<u64>,
+    /// The Unix timestamp (in seconds) for when the job is estimated to finish.
+    #[serde(default)]
+    pub estimated_finish: Option<u64>,
+    /// The hyperparameters used for the fine-tuning job, for supervised jobs.
+    #[serde(default)]
+    pub hyperparameters: Option<Hyperparameters>,
+    /// The method used for fine-tuning.
+    #[serde(default)]
+    pub method: Option<FineTuningMethod>,
+    /// The base model that is being fine-tuned.
+    pub model: String,
+    /// The organization that owns the fine-tuning job.
+    pub organization_id: String,
+    /// The compiled results file ID(s) for the fine-tuning job.
+    #[serde(default)]
+    pub result_files: Vec<String>,
+    /// The seed used for the fine-tuning job.
+    #[serde(default)]
+    pub seed: Option<i64>,
+    pub status: FineTuningJobStatus,
+    /// The total number of billable tokens processed by this fine-tuning job. Null while the job is still running.
+    #[serde(default)]
+    pub trained_tokens: Option<u64>,
+    /// The file ID used for training.
+    pub training_file: String,
+    /// The file ID used for validation.
+    #[serde(default)]
+    pub validation_file: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct FineTuningJobError {
+    /// A machine-readable error code.
+    pub code: String,
+    /// A human-readable error message.
+    pub message: String,
+    /// The parameter that was invalid, usually training_file or validation_file.
+    #[serde(default)]
+    pub param: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FineTuningJobStatus {
+    ValidatingFiles,
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+    /// A status this SDK does not know yet, such as `paused`. It is not terminal.
+    #[serde(other)]
+    Unknown,
+}
+
+impl FineTuningJobStatus {
+    /// Whether the job has stopped and its status will not change anymore.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            FineTuningJobStatus::Succeeded | FineTuningJobStatus::Failed | FineTuningJobStatus::Cancelled
+        )
+    }
+}
+
+/// A status message of a fine-tuning job.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FineTuningJobEvent {
+    pub id: String,
+    /// The object type, which is always "fine_tuning.job.event".
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the event was created.
+    pub created_at: u64,
+    /// The log level of the event: info, warn or error.
+    pub level: String,
+    pub message: String,
+    /// The type of event: message or metrics.
+    #[serde(default)]
+    pub r#type: Option<String>,
+    /// The data associated with the event, e.g. the training metrics of a step.
+    #[serde(default)]
+    pub data: Option<Value>,
+}
+
+/// A model checkpoint saved at the end of a training epoch.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FineTuningCheckpoint {
+    pub id: String,
+    /// The object type, which is always "fine_tuning.job.checkpoint".
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the checkpoint was created.
+    pub created_at: u64,
+    /// The name of the fine-tuned checkpoint model that is created.
+    pub fine_tuned_model_checkpoint: String,
+    /// The name of the fine-tuning job that this checkpoint was created from.
+    pub fine_tuning_job_id: String,
+    /// The step number that the checkpoint was created at.
+    pub step_number: u64,
+    /// Metrics at the step number during the fine-tuning job.
+    pub metrics: FineTuningCheckpointMetrics,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct FineTuningCheckpointMetrics {
+    #[serde(default)]
+    pub step: Option<f64>,
+    #[serde(default)]
+    pub train_loss: Option<f64>,
+    #[serde(default)]
+    pub train_mean_token_accuracy: Option<f64>,
+    #[serde(default)]
+    pub valid_loss: Option<f64>,
+    #[serde(default)]
+    pub valid_mean_token_accuracy: Option<f64>,
+    #[serde(default)]
+    pub full_valid_loss: Option<f64>,
+    #[serde(default)]
+    pub full_valid_mean_token_accuracy: Option<f64>,
+}
+
+pub type ListFineTuningJobsResponse = ListResponse<FineTuningJob>;
+pub type ListFineTuningEventsResponse = ListResponse<FineTuningJobEvent>;
+pub type ListFineTuningCheckpointsResponse = ListResponse<FineTuningCheckpoint>;
+
+/// What `FineTuningJob::wait_for_completion` reports, in the order it happened.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FineTuningJobUpdate {
+    /// A new event of the job.
+    Event(FineTuningJobEvent),
+    /// The job after its status changed. The last update is the job in a terminal status.
+    Status(Box<FineTuningJob>),
+}
+
+struct WaitState<'a> {
+    sdk: &'a LLMSDK,
+    id: String,
+    poll: PollOptions,
+    interval: Duration,
+    last_event: Option<String>,
+    last_status: Option<FineTuningJobStatus>,
+    pending: Vec<FineTuningJobUpdate>,
+    first: bool,
+    done: bool,
+}
+
+impl FineTuningJob {
+    /// Polls the job until it succeeds, fails or is cancelled, yielding its new events and every
+    /// status change. Events from before the call are skipped. The stream ends with the job in
+    /// its terminal status, or after the first error.
+    pub fn wait_for_completion<'a>(
+        &self,
+        sdk: &'a LLMSDK,
+        poll: PollOptions,
+    ) -> BoxStream<'a, Result<FineTuningJobUpdate>> {
+        let state = WaitState {
+            sdk,
+            id: self.id.clone(),
+            poll,
+            interval: poll.initial_interval,
+            last_event: None,
+            last_status: Some(self.status),
+            pending: vec![],
+            first: true,
+            done: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(update) = state.pending.pop() {
+                    return Some((Ok(update), state));
+                }
+                if state.done {
+                    return None;
+                }
+                if !state.first {
+                    tokio::time::sleep(state.interval).await;
+                }
+                match state.poll_once().await {
+                    Ok(changed) => state.interval = state.poll.next_interval(state.interval, changed),
+                    Err(e) => {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                }
+                state.first = false;
+            }
+        })
+        .boxed()
+    }
+}
+
+impl WaitState<'_> {
+    /// Fetches the job and then its new events, queuing the updates. Returns whether there were
+    /// any. The job goes first so the events that led to a terminal status are not missed.
+    async fn poll_once(&mut self) -> Result<bool> {
+        let job = self.sdk.retrieve_fine_tuning_job(&self.id).await?;
+
+        // events come newest first; page back to the last seen one, but skip the history on the
+        // first poll
+        let first_poll = self.first;
+        let mut new_events = vec![];
+        let mut after = None;
+        loop {
+            let page = self
+                .sdk
+                .list_fine_tuning_events(ListFineTuningEventsRequest {
+                    after: after.take(),
+                    limit: Some(100),
+                    ..ListFineTuningEventsRequest::new(&self.id)
+                })
+                .await?;
+            let next = page.next_cursor(|event| &event.id).map(str::to_string);
+            let seen = page.data.iter().position(|event| Some(&event.id) == self.last_event.as_ref());
+            let reached = seen.is_some();
+            new_events.extend(page.data.into_iter().take(seen.unwrap_or(usize::MAX)));
+            match next {
+                Some(next) if !reached && !first_poll => after = Some(next),
+                _ => break,
+            }
+        }
+        if let Some(newest) = new_events.first() {
+            self.last_event = Some(newest.id.clone());
+        }
+
+        // `pending` is popped from the back, so the latest update goes in first
+        let mut updates = vec![];
+        let status_changed = self.last_status != Some(job.status);
+        if status_changed || job.status.is_terminal() {
+            self.last_status = Some(job.status);
+            self.done = job.status.is_terminal();
+            updates.push(FineTuningJobUpdate::Status(Box::new(job)));
+        }
+        if !first_poll {
+            updates.extend(new_events.into_iter().map(FineTuningJobUpdate::Event));
+        }
+        let changed = !updates.is_empty();
+        self.pending = updates;
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn job(status: &str) -> serde_json::Value {
+        json!({
+            "object": "fine_tuning.job",
+            "id": "ftjob-abc123",
+            "model": "gpt-4o-mini-2024-07-18",
+            "created_at": 1721764800,
+            "fine_tuned_model": if status == "succeeded" { json!("ft:gpt-4o-mini:acme::xyz") } else { json!(null) },
+            "organization_id": "org-123",
+            "result_files": [],
+            "status": status,
+            "validation_file": null,
+            "training_file": "file-abc123",
+            "hyperparameters": { "batch_size": "auto", "learning_rate_multiplier": 1.8, "n_epochs": 3 },
+            "method": {
+                "type": "supervised",
+                "supervised": { "hyperparameters": { "batch_size": "auto", "learning_rate_multiplier": 1.8, "n_epochs": 3 } }
+            },
+            "seed": 42
+        })
+    }
+
+    fn events(ids: &[u32]) -> MockResponse {
+        events_page(ids, false)
+    }
+
+    fn events_page(ids: &[u32], has_more: bool) -> MockResponse {
+        let data: Vec<_> = ids
+            .iter()
+            .map(|id| json!({ "object": "fine_tuning.job.event", "id": format!("ftevent-{id}"), "created_at": 1721764800 + id, "level": "info", "message": format!("Step {id}"), "type": "message" }))
+            .collect();
+        MockResponse::json(json!({ "object": "list", "data": data, "has_more": has_more }))
+    }
+
+    #[test]
+    fn test_create_fine_tuning_job_serialize() -> Result<()> {
+        let req = CreateFineTuningJobRequest {
+            suffix: Some("weekly".into()),
+            method: Some(FineTuningMethod::dpo(DpoHyperparameters {
+                beta: Some(Hyperparameter::Value(0.1)),
+                n_epochs: Some(Hyperparameter::Auto),
+                ..Default::default()
+            })),
+            ..CreateFineTuningJobRequest::new("gpt-4o-mini", "file-abc123")
+        };
+        assert_eq!(
+            serde_json::to_value(&req)?,
+            json!({
+                "model": "gpt-4o-mini",
+                "training_file": "file-abc123",
+                "suffix": "weekly",
+                "method": { "type": "dpo", "dpo": { "hyperparameters": { "beta": 0.1, "n_epochs": "auto" } } }
+            })
+        );
+        let job: FineTuningJob = serde_json::from_value(job("running"))?;
+        let hyperparameters = job.hyperparameters.unwrap();
+        assert_eq!(hyperparameters.batch_size, Some(Hyperparameter::Auto));
+        assert_eq!(hyperparameters.n_epochs, Some(Hyperparameter::Value(3)));
+        assert!(matches!(job.method, Some(FineTuningMethod::Supervised { .. })));
+        assert!(serde_json::from_value::<Hyperparameter<u32>>(json!("many")).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fine_tuning_jobs_api() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::json(job("validating_files")),
+            MockResponse::json(json!({ "object": "list", "data": [job("running"), job("paused")], "has_more": false })),
+            MockResponse::json(job("cancelled")),
+            MockResponse::json(json!({
+                "object": "list",
+                "data": [{
+                    "object": "fine_tuning.job.checkpoint",
+                    "id": "ftckpt-1",
+                    "created_at": 1721764867,
+                    "fine_tuned_model_checkpoint": "ft:gpt-4o-mini:acme::xyz:ckpt-step-88",
+                    "fine_tuning_job_id": "ftjob-abc123",
+                    "metrics": { "step": 88.0, "train_loss": 0.47, "train_mean_token_accuracy": 0.9 },
+                    "step_number": 88
+                }],
+                "first_id": "ftckpt-1",
+                "last_id": "ftckpt-1",
+                "has_more": false
+            })),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let job = sdk
+            .create_fine_tuning_job(CreateFineTuningJobRequest::new("gpt-4o-mini", "file-abc123"))
+            .await?;
+        assert_eq!(job.status, FineTuningJobStatus::ValidatingFiles);
+        let jobs = sdk.list_fine_tuning_jobs(ListFineTuningJobsRequest { limit: Some(1), ..Default::default() }).await?;
+        assert_eq!(jobs.data[0].status, FineTuningJobStatus::Running);
+        assert_eq!(jobs.data[1].status, FineTuningJobStatus::Unknown);
+        assert!(!jobs.data[1].status.is_terminal());
+        assert!(sdk.cancel_fine_tuning_job(&job.id).await?.status.is_terminal());
+        let checkpoints = sdk
+            .list_fine_tuning_checkpoints(ListFineTuningCheckpointsRequest::new(&job.id))
+            .await?;
+        assert_eq!(checkpoints.data[0].metrics.train_loss, Some(0.47));
+
+        assert_eq!(
+            server.calls(),
+            [
+                "POST /fine_tuning/jobs",
+                "GET /fine_tuning/jobs?limit=1",
+                "POST /fine_tuning/jobs/ftjob-abc123/cancel",
+                "GET /fine_tuning/jobs/ftjob-abc123/checkpoints",
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_completion() -> Result<()> {
+        let server = MockServer::start(vec![
+            // first poll: history is skipped
+            MockResponse::json(job("queued")),
+            events_page(&[2, 1], true),
+            // nothing new
+            MockResponse::json(job("queued")),
+            events(&[2, 1]),
+            // more new events than fit a page
+            MockResponse::json(job("running")),
+            events_page(&[5, 4], true),
+            events_page(&[3, 2], true),
+            // the events that came with the terminal status are still reported
+            MockResponse::json(job("succeeded")),
+            events(&[6, 5, 4, 3, 2, 1]),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let job: FineTuningJob = serde_json::from_value(job("validating_files"))?;
+        let poll = PollOptions::default().with_interval(Duration::from_millis(1), Duration::from_millis(5));
+        let updates: Vec<_> = job.wait_for_completion(&sdk, poll).collect().await;
+        let updates = updates.into_iter().collect::<Result<Vec<_>, _>>()?;
+        let summary: Vec<String> = updates
+            .iter()
+            .map(|update| match update {
+                FineTuningJobUpdate::Event(event) => event.message.clone(),
+                FineTuningJobUpdate::Status(job) => format!("{:?}", job.status),
+            })
+            .collect();
+        assert_eq!(summary, ["Queued", "Step 3", "Step 4", "Step 5", "Running", "Step 6", "Succeeded"]);
+        let FineTuningJobUpdate::Status(last) = updates.last().unwrap() else {
+            panic!("the last update is the job");
+        };
+        assert_eq!(last.fine_tuned_model.as_deref(), Some("ft:gpt-4o-mini:acme::xyz"));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 9);
+        assert_eq!(requests[1].path, "/fine_tuning/jobs/ftjob-abc123/events?limit=100");
+        assert_eq!(requests[6].path, "/fine_tuning/jobs/ftjob-abc123/events?after=ftevent-4&limit=100");
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// Sort order by the created_at timestamp of the objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListOrder {
+    Asc,
+    Desc,
+}
+
+/// One page of a cursor-paginated list endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListResponse<T> {
+    /// The object type, which is always "list".
+    pub object: String,
+    pub data: Vec<T>,
+    #[serde(default)]
+    pub first_id: Option<String>,
+    #[serde(default)]
+    pub last_id: Option<String>,
+    /// Whether there are more objects after this page.
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+impl<T> ListResponse<T> {
+    /// The `after` cursor of the next page, `None` on the last page. Not every endpoint returns
+    /// `last_id`, so the id of the last object is used as fallback.
+    pub fn next_cursor<'a>(&'a self, id: impl Fn(&'a T) -> &'a str) -> Option<&'a str> {
+        if !self.has_more {
+            return None;
+        }
+        self.last_id.as_deref().or_else(|| self.data.last().map(id))
+    }
+}
@@ -1,5 +1,41 @@
+mod assistants;
+mod batches;
 mod chat_completion;
+mod create_embedding;
 mod create_image;
+mod create_image_edit;
+mod create_image_variation;
+mod create_moderation;
+mod create_speech;
+mod create_transcription;
+mod create_translation;
+mod files;
+mod fine_tuning;
+mod list;
+mod models;
+mod realtime;
+mod responses;
+mod runs;
+mod threads;
+mod vector_stores;
 
+pub use assistants::*;
+pub use batches::*;
 pub use chat_completion::*;
-pub use create_image::*;
\ No newline at end of file
+pub use create_embedding::*;
+pub use create_image::*;
+pub use create_image_edit::*;
+pub use create_image_variation::*;
+pub use create_moderation::*;
+pub use create_speech::*;
+pub use create_transcription::*;
+pub use create_translation::*;
+pub use files::*;
+pub use fine_tuning::*;
+pub use list::*;
+pub use models::*;
+pub use realtime::*;
+pub use responses::*;
+pub use runs::*;
+pub use threads::*;
+pub use vector_stores::*;
@@ -0,0 +1,149 @@
+use reqwest::RequestBuilder;
+use serde::Deserialize;
+
+use crate::{encode_path_segment, IntoRequest, RequestContext};
+
+/// Lists the currently available models.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListModelsRequest;
+
+/// Retrieves a model instance, providing basic information about the model.
+#[derive(Debug, Clone)]
+pub struct RetrieveModelRequest {
+    /// The ID of the model to use for this request.
+    pub id: String,
+}
+
+/// Deletes a fine-tuned model. You must have the Owner role in your organization to delete a model.
+#[derive(Debug, Clone)]
+pub struct DeleteModelRequest {
+    /// The model to delete.
+    pub id: String,
+}
+
+impl RetrieveModelRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        RetrieveModelRequest { id: id.into() }
+    }
+}
+
+impl DeleteModelRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        DeleteModelRequest { id: id.into() }
+    }
+}
+
+impl IntoRequest for ListModelsRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get("models")
+    }
+}
+
+impl IntoRequest for RetrieveModelRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("models/{}", encode_path_segment(&self.id)))
+    }
+}
+
+impl IntoRequest for DeleteModelRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.delete(&format!("models/{}", encode_path_segment(&self.id)))
+    }
+}
+
+/// Describes a model that can be used with the API.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Model {
+    /// The model identifier, which can be referenced in the API endpoints.
+    pub id: String,
+    /// The object type, which is always "model".
+    pub object: String,
+    /// The Unix timestamp (in seconds) when the model was created.
+    pub created: u64,
+    /// The organization that owns the model.
+    pub owned_by: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListModelsResponse {
+    /// The object type, which is always "list".
+    pub object: String,
+    pub data: Vec<Model>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DeleteModelResponse {
+    /// The ID of the deleted model.
+    pub id: String,
+    /// The object type, which is always "model".
+    pub object: String,
+    /// Whether the model was deleted.
+    pub deleted: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_models_api() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::json(json!({
+                "object": "list",
+                "data": [
+                    { "id": "gpt-4o-mini", "object": "model", "created": 1721172741, "owned_by": "system" },
+                    { "id": "ft:gpt-4o-mini:acme::abc123", "object": "model", "created": 1731172741, "owned_by": "acme" }
+                ]
+            })),
+            MockResponse::json(json!({ "id": "gpt-4o-mini", "object": "model", "created": 1721172741, "owned_by": "system" })),
+            MockResponse::json(json!({ "id": "ft:gpt-4o-mini:acme::abc123", "object": "model", "deleted": true })),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+
+        let models = sdk.list_models().await?;
+        assert_eq!(models.data.len(), 2);
+        assert_eq!(models.data[1].owned_by, "acme");
+        let model = sdk.retrieve_model("gpt-4o-mini").await?;
+        assert_eq!(model.created, 1721172741);
+        let deleted = sdk.delete_model("ft:gpt-4o-mini:acme::abc123").await?;
+        assert!(deleted.deleted);
+
+        assert_eq!(
+            server.calls(),
+            [
+                "GET /models",
+                "GET /models/gpt-4o-mini",
+                "DELETE /models/ft:gpt-4o-mini:acme::abc123",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_model_id_is_escaped() {
+        let ctx = RequestContext::new("https://api.openai.com/v1", reqwest::Client::new());
+        let req = RetrieveModelRequest::new("../files?x").into_request(&ctx).build().unwrap();
+        assert_eq!(req.url().as_str(), "https://api.openai.com/v1/models/..%2Ffiles%3Fx");
+        for (id, escaped) in [("..", "%252E%252E"), (".", "%252E"), ("...", "%252E%252E%252E")] {
+            let req = DeleteModelRequest::new(id).into_request(&ctx).build().unwrap();
+            assert_eq!(req.url().as_str(), format!("https://api.openai.com/v1/models/{escaped}"));
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_list_models_live() -> Result<()> {
+        let sdk = LLMSDK::new(std::env::var("OPENAI_API_KEY")?);
+        let models = sdk.list_models().await?;
+        assert!(!models.data.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,789 @@
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures_util::{
+    stream::{self, BoxStream, SplitSink, SplitStream},
+    SinkExt, Stream, StreamExt,
+};
+use reqwest::{header::HeaderMap, Url};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio_tungstenite::tungstenite::{
+    self,
+    protocol::{frame::coding::CloseCode, CloseFrame},
+    Message,
+};
+
+use crate::{
+    websocket::{self, WebSocket},
+    ApiError, LlmError, Result, Tool,
+};
+
+/// The `OpenAI-Beta` header value of the Realtime API events below.
+pub(crate) const REALTIME_BETA: &str = "realtime=v1";
+
+/// A duplex session with a realtime model, opened by `LLMSDK::realtime`. Client events are sent
+/// with `send`; server events are read by polling the session as a stream. Use `split` to send
+/// and receive from different tasks.
+pub struct RealtimeSession {
+    sender: RealtimeSender,
+    events: RealtimeEvents,
+}
+
+/// The sending half of a `RealtimeSession`. Clones share the connection.
+#[derive(Clone)]
+pub struct RealtimeSender {
+    sink: Arc<Mutex<SplitSink<WebSocket, Message>>>,
+}
+
+/// The receiving half of a `RealtimeSession`.
+pub type RealtimeEvents = BoxStream<'static, Result<RealtimeServerEvent>>;
+
+impl RealtimeSession {
+    pub(crate) async fn connect(url: &Url, headers: &HeaderMap) -> Result<Self> {
+        let (sink, stream) = websocket::connect(url, headers).await?.split();
+        let sender = RealtimeSender {
+            sink: Arc::new(Mutex::new(sink)),
+        };
+        let events = server_events(stream, sender.clone()).boxed();
+        Ok(RealtimeSession { sender, events })
+    }
+
+    pub async fn send(&self, event: &RealtimeClientEvent) -> Result<()> {
+        self.sender.send(event).await
+    }
+
+    /// See `RealtimeSender::submit_function_output`.
+    pub async fn submit_function_output(&self, call_id: impl Into<String>, output: impl Into<String>) -> Result<()> {
+        self.sender.submit_function_output(call_id, output).await
+    }
+
+    /// Starts closing the session. The event stream ends once the server confirms.
+    pub async fn close(&self) -> Result<()> {
+        self.sender.close().await
+    }
+
+    pub fn sender(&self) -> RealtimeSender {
+        self.sender.clone()
+    }
+
+    pub fn split(self) -> (RealtimeSender, RealtimeEvents) {
+        (self.sender, self.events)
+    }
+}
+
+impl Stream for RealtimeSession {
+    type Item = Result<RealtimeServerEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_next_unpin(cx)
+    }
+}
+
+impl std::fmt::Debug for RealtimeSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RealtimeSession").finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for RealtimeSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RealtimeSender").finish_non_exhaustive()
+    }
+}
+
+impl RealtimeSender {
+    /// Sends a client event. Fails once the session is closing.
+    pub async fn send(&self, event: &RealtimeClientEvent) -> Result<()> {
+        let text = serde_json::to_string(event).map_err(|e| LlmError::Validation(e.to_string()))?;
+        self.sink.lock().await.send(Message::Text(text)).await?;
+        Ok(())
+    }
+
+    /// Sends the output of a function call the model made, then asks for a new response so the
+    /// model can use it.
+    pub async fn submit_function_output(&self, call_id: impl Into<String>, output: impl Into<String>) -> Result<()> {
+        self.send(&RealtimeClientEvent::function_call_output(call_id, output)).await?;
+        self.send(&RealtimeClientEvent::create_response()).await
+    }
+
+    /// Starts closing the session. Does nothing if the session is already closing or closed.
+    pub async fn close(&self) -> Result<()> {
+        let frame = CloseFrame {
+            code: CloseCode::Normal,
+            reason: "".into(),
+        };
+        match self.sink.lock().await.send(Message::Close(Some(frame))).await {
+            Ok(()) | Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Parses text messages into events. Pings and the server's close are answered by tungstenite.
+/// The stream ends after a normal close, or after the first error.
+fn server_events(
+    stream: SplitStream<WebSocket>,
+    sender: RealtimeSender,
+) -> impl Stream<Item = Result<RealtimeServerEvent>> {
+    stream::unfold(Some((stream, sender)), |state| async move {
+        let (mut stream, sender) = state?;
+        loop {
+            match stream.next().await? {
+                Ok(Message::Text(text)) => {
+                    let event = serde_json::from_str(&text).map_err(|source| LlmError::Deserialize { source, body: text });
+                    return Some((event, Some((stream, sender))));
+                }
+                Ok(Message::Close(frame)) => {
+                    // the answer to a close the server started is only queued; send it out. The
+                    // connection is going away either way, so a failed write doesn't matter
+                    let _ = sender.sink.lock().await.flush().await;
+                    return match frame {
+                        Some(frame) if frame.code != CloseCode::Normal => {
+                            let err = LlmError::WebSocket(format!("closed with code {}: {}", frame.code, frame.reason));
+                            Some((Err(err), None))
+                        }
+                        _ => None,
+                    };
+                }
+                Ok(_) => {}
+                Err(e) => return Some((Err(e.into()), None)),
+            }
+        }
+    })
+}
+
+/// An event sent by the client.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum RealtimeClientEvent {
+    /// Updates the session configuration. Only the fields that are set are changed.
+    #[serde(rename = "session.update")]
+    SessionUpdate { session: RealtimeSessionConfig },
+    /// Appends base64-encoded audio, in the session's input format, to the input buffer.
+    #[serde(rename = "input_audio_buffer.append")]
+    InputAudioBufferAppend { audio: String },
+    /// Commits the input buffer as a user message. Not needed when turn detection is on.
+    #[serde(rename = "input_audio_buffer.commit")]
+    InputAudioBufferCommit,
+    #[serde(rename = "input_audio_buffer.clear")]
+    InputAudioBufferClear,
+    #[serde(rename = "conversation.item.create")]
+    ConversationItemCreate {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        previous_item_id: Option<String>,
+        item: RealtimeItem,
+    },
+    #[serde(rename = "conversation.item.delete")]
+    ConversationItemDelete { item_id: String },
+    /// Asks the model for a response. Not needed after speech when turn detection is on.
+    #[serde(rename = "response.create")]
+    ResponseCreate {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        response: Option<RealtimeResponseConfig>,
+    },
+    #[serde(rename = "response.cancel")]
+    ResponseCancel,
+}
+
+impl RealtimeClientEvent {
+    pub fn update_session(session: RealtimeSessionConfig) -> Self {
+        RealtimeClientEvent::SessionUpdate { session }
+    }
+
+    /// Appends raw audio (16-bit PCM, 24kHz, mono, little-endian with the default format).
+    pub fn append_audio(audio: &[u8]) -> Self {
+        RealtimeClientEvent::InputAudioBufferAppend {
+            audio: STANDARD.encode(audio),
+        }
+    }
+
+    pub fn create_item(item: RealtimeItem) -> Self {
+        RealtimeClientEvent::ConversationItemCreate {
+            previous_item_id: None,
+            item,
+        }
+    }
+
+    pub fn function_call_output(call_id: impl Into<String>, output: impl Into<String>) -> Self {
+        Self::create_item(RealtimeItem::FunctionCallOutput {
+            id: None,
+            call_id: call_id.into(),
+            output: output.into(),
+        })
+    }
+
+    pub fn create_response() -> Self {
+        RealtimeClientEvent::ResponseCreate { response: None }
+    }
+}
+
+/// The configuration of a session. `session.created` and `session.updated` carry the whole
+/// effective configuration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RealtimeSessionConfig {
+    /// "text" and/or "audio".
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modalities: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// The voice of audio output, e.g. "alloy". Cannot change once the model has spoken.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_audio_format: Option<RealtimeAudioFormat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_audio_format: Option<RealtimeAudioFormat>,
+    /// Transcribes input audio, asynchronously, with the given model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_audio_transcription: Option<InputAudioTranscription>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_detection: Option<TurnDetection>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<RealtimeTool>,
+    /// "auto", "none", "required", or the name of a function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// A number of tokens, or "inf".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_output_tokens: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RealtimeAudioFormat {
+    Pcm16,
+    G711Ulaw,
+    G711Alaw,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputAudioTranscription {
+    /// e.g. "whisper-1" or "gpt-4o-transcribe".
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+/// How the server detects the end of the user's turn, to commit the audio and respond.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TurnDetection {
+    /// Voice activity detection based on audio volume.
+    ServerVad {
+        /// Activation threshold, between 0 and 1.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        threshold: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prefix_padding_ms: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        silence_duration_ms: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        create_response: Option<bool>,
+    },
+    /// Detection based on what the user said.
+    SemanticVad {
+        /// "low", "medium", "high" or "auto".
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        eagerness: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        create_response: Option<bool>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RealtimeTool {
+    Function {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        parameters: serde_json::Value,
+    },
+}
+
+impl From<Tool> for RealtimeTool {
+    fn from(tool: Tool) -> Self {
+        RealtimeTool::Function {
+            name: tool.function.name,
+            description: tool.function.description,
+            parameters: tool.function.parameters,
+        }
+    }
+}
+
+/// Overrides the session configuration for one response.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RealtimeResponseConfig {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub modalities: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<RealtimeTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<String>,
+    /// "none" to generate the response outside of the conversation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversation: Option<String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+/// An item of the conversation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RealtimeItem {
+    Message {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        role: RealtimeRole,
+        content: Vec<RealtimeContent>,
+    },
+    FunctionCall {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        call_id: String,
+        name: String,
+        /// The arguments of the call, as a JSON string.
+        arguments: String,
+    },
+    FunctionCallOutput {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        call_id: String,
+        output: String,
+    },
+}
+
+impl RealtimeItem {
+    pub fn user_text(text: impl Into<String>) -> Self {
+        RealtimeItem::Message {
+            id: None,
+            role: RealtimeRole::User,
+            content: vec![RealtimeContent::InputText { text: text.into() }],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RealtimeRole {
+    User,
+    Assistant,
+    System,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RealtimeContent {
+    InputText {
+        text: String,
+    },
+    InputAudio {
+        /// Base64-encoded audio.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        audio: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transcript: Option<String>,
+    },
+    Text {
+        text: String,
+    },
+    Audio {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        audio: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transcript: Option<String>,
+    },
+    /// Another item of the conversation, by id.
+    ItemReference {
+        id: String,
+    },
+    /// A content type this SDK does not know yet.
+    #[serde(other)]
+    Other,
+}
+
+/// A response of the model, as carried by `response.created` and `response.done`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RealtimeResponse {
+    pub id: String,
+    /// The object type, which is always "realtime.response".
+    pub object: String,
+    /// "in_progress", "completed", "cancelled", "failed" or "incomplete".
+    pub status: String,
+    #[serde(default)]
+    pub status_details: Option<serde_json::Value>,
+    #[serde(default)]
+    pub output: Vec<RealtimeItem>,
+    #[serde(default)]
+    pub usage: Option<RealtimeUsage>,
+}
+
+impl RealtimeResponse {
+    /// The function calls of the response, as `(call_id, name, arguments)`. Answer them with
+    /// `RealtimeSender::submit_function_output`.
+    pub fn function_calls(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.output.iter().filter_map(|item| match item {
+            RealtimeItem::FunctionCall {
+                call_id,
+                name,
+                arguments,
+                ..
+            } => Some((call_id.as_str(), name.as_str(), arguments.as_str())),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct RealtimeUsage {
+    pub total_tokens: u32,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
+/// An event sent by the server. Event types this SDK does not know are kept as `Other`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type")]
+pub enum RealtimeServerEvent {
+    /// A client event was rejected, or the server had an error. The session stays open.
+    #[serde(rename = "error")]
+    Error { error: ApiError },
+    #[serde(rename = "session.created")]
+    SessionCreated { session: RealtimeSessionConfig },
+    #[serde(rename = "session.updated")]
+    SessionUpdated { session: RealtimeSessionConfig },
+    #[serde(rename = "conversation.item.created")]
+    ConversationItemCreated {
+        #[serde(default)]
+        previous_item_id: Option<String>,
+        item: RealtimeItem,
+    },
+    #[serde(rename = "conversation.item.input_audio_transcription.completed")]
+    InputAudioTranscriptionCompleted {
+        item_id: String,
+        content_index: usize,
+        transcript: String,
+    },
+    #[serde(rename = "input_audio_buffer.committed")]
+    InputAudioBufferCommitted {
+        #[serde(default)]
+        previous_item_id: Option<String>,
+        item_id: String,
+    },
+    #[serde(rename = "input_audio_buffer.cleared")]
+    InputAudioBufferCleared,
+    #[serde(rename = "input_audio_buffer.speech_started")]
+    SpeechStarted { audio_start_ms: u64, item_id: String },
+    #[serde(rename = "input_audio_buffer.speech_stopped")]
+    SpeechStopped { audio_end_ms: u64, item_id: String },
+    #[serde(rename = "response.created")]
+    ResponseCreated { response: RealtimeResponse },
+    #[serde(rename = "response.done")]
+    ResponseDone { response: RealtimeResponse },
+    #[serde(rename = "response.output_item.added")]
+    ResponseOutputItemAdded {
+        response_id: String,
+        output_index: usize,
+        item: RealtimeItem,
+    },
+    #[serde(rename = "response.output_item.done")]
+    ResponseOutputItemDone {
+        response_id: String,
+        output_index: usize,
+        item: RealtimeItem,
+    },
+    #[serde(rename = "response.text.delta")]
+    ResponseTextDelta {
+        response_id: String,
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        delta: String,
+    },
+    #[serde(rename = "response.text.done")]
+    ResponseTextDone {
+        response_id: String,
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        text: String,
+    },
+    /// A chunk of base64-encoded output audio.
+    #[serde(rename = "response.audio.delta")]
+    ResponseAudioDelta {
+        response_id: String,
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        delta: String,
+    },
+    #[serde(rename = "response.audio.done")]
+    ResponseAudioDone {
+        response_id: String,
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+    },
+    #[serde(rename = "response.audio_transcript.delta")]
+    ResponseAudioTranscriptDelta {
+        response_id: String,
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        delta: String,
+    },
+    #[serde(rename = "response.audio_transcript.done")]
+    ResponseAudioTranscriptDone {
+        response_id: String,
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        transcript: String,
+    },
+    #[serde(rename = "response.function_call_arguments.delta")]
+    ResponseFunctionCallArgumentsDelta {
+        response_id: String,
+        item_id: String,
+        output_index: usize,
+        call_id: String,
+        delta: String,
+    },
+    #[serde(rename = "response.function_call_arguments.done")]
+    ResponseFunctionCallArgumentsDone {
+        response_id: String,
+        item_id: String,
+        output_index: usize,
+        call_id: String,
+        /// Not sent by every API version; the function call item always has it.
+        #[serde(default)]
+        name: Option<String>,
+        arguments: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer, MockWebSocketServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn events(events: &[serde_json::Value]) -> Vec<Message> {
+        events.iter().map(|event| Message::Text(event.to_string())).collect()
+    }
+
+    fn response(status: &str, output: serde_json::Value) -> serde_json::Value {
+        json!({ "id": "resp_1", "object": "realtime.response", "status": status, "output": output, "usage": null })
+    }
+
+    #[tokio::test]
+    async fn test_realtime_function_call_round_trip() -> Result<()> {
+        let call = json!({ "id": "item_2", "type": "function_call", "call_id": "call_1", "name": "get_weather",
+                           "arguments": "{\"city\":\"Paris\"}", "status": "completed" });
+        let answer = json!({ "id": "item_4", "type": "message", "role": "assistant", "status": "completed",
+                             "content": [{ "type": "text", "text": "It is 22C." }] });
+        let text_delta = |delta: &str| {
+            json!({ "type": "response.text.delta", "event_id": "ev", "response_id": "resp_2", "item_id": "item_4",
+                    "output_index": 0, "content_index": 0, "delta": delta })
+        };
+        let replay = vec![
+            events(&[json!({ "type": "session.created", "event_id": "ev_1",
+                             "session": { "id": "sess_1", "object": "realtime.session", "model": "gpt-4o-realtime-preview",
+                                          "modalities": ["text", "audio"], "voice": "alloy", "tools": [],
+                                          "max_response_output_tokens": "inf", "turn_detection": null } })]),
+            events(&[json!({ "type": "session.updated", "session": { "modalities": ["text"] } })]),
+            events(&[json!({ "type": "conversation.item.created", "previous_item_id": null,
+                             "item": { "id": "item_1", "type": "message", "role": "user",
+                                       "content": [{ "type": "input_text", "text": "Weather in Paris?" }] } })]),
+            [vec![Message::Ping(b"hb".to_vec())], events(&[
+                json!({ "type": "response.created", "response": response("in_progress", json!([])) }),
+                json!({ "type": "response.function_call_arguments.delta", "response_id": "resp_1", "item_id": "item_2",
+                        "output_index": 0, "call_id": "call_1", "delta": "{\"city\":" }),
+                json!({ "type": "response.function_call_arguments.done", "response_id": "resp_1", "item_id": "item_2",
+                        "output_index": 0, "call_id": "call_1", "arguments": "{\"city\":\"Paris\"}" }),
+                json!({ "type": "rate_limits.updated", "rate_limits": [] }),
+                json!({ "type": "response.done", "response": response("completed", json!([call])) }),
+            ])]
+            .concat(),
+            events(&[json!({ "type": "conversation.item.created", "item": {
+                "id": "item_3", "type": "function_call_output", "call_id": "call_1", "output": "22C" } })]),
+            events(&[
+                text_delta("It is "),
+                text_delta("22C."),
+                json!({ "type": "response.done", "response": response("completed", json!([answer])) }),
+            ]),
+        ];
+        let mut server = MockWebSocketServer::start(replay).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).token("sk-test").build()?;
+
+        let mut session = sdk.realtime("gpt-4o-realtime-preview").await?;
+        let Some(Ok(RealtimeServerEvent::SessionCreated { session: config })) = session.next().await else {
+            panic!("the session should start with session.created");
+        };
+        assert_eq!(config.voice.as_deref(), Some("alloy"));
+        let config = RealtimeSessionConfig {
+            modalities: vec!["text".into()],
+            tools: vec![Tool::function("get_weather", None, json!({ "type": "object" })).into()],
+            ..Default::default()
+        };
+        session.send(&RealtimeClientEvent::update_session(config)).await?;
+        assert!(matches!(session.next().await, Some(Ok(RealtimeServerEvent::SessionUpdated { .. }))));
+        session.send(&RealtimeClientEvent::create_item(RealtimeItem::user_text("Weather in Paris?"))).await?;
+        session.next().await.unwrap()?;
+        session.send(&RealtimeClientEvent::create_response()).await?;
+
+        let mut text = String::new();
+        let mut responses = 0;
+        while let Some(event) = session.next().await {
+            match event? {
+                RealtimeServerEvent::ResponseDone { response } => {
+                    responses += 1;
+                    let calls: Vec<_> = response.function_calls().collect();
+                    if responses == 2 {
+                        assert!(calls.is_empty());
+                        session.close().await?;
+                        let err = session.send(&RealtimeClientEvent::create_response()).await.unwrap_err();
+                        assert!(matches!(err, LlmError::WebSocket(_)));
+                        continue;
+                    }
+                    assert_eq!(calls, [("call_1", "get_weather", "{\"city\":\"Paris\"}")]);
+                    session.submit_function_output(calls[0].0, "22C").await?;
+                }
+                RealtimeServerEvent::ResponseTextDelta { delta, .. } => text.push_str(&delta),
+                _ => {}
+            }
+        }
+        assert_eq!(text, "It is 22C.");
+        drop(session);
+        server.finished().await;
+
+        let head = server.head();
+        assert!(head.starts_with("GET /realtime?model=gpt-4o-realtime-preview HTTP/1.1\r\n"));
+        assert!(head.contains("authorization: Bearer sk-test\r\n"));
+        assert!(head.contains("openai-beta: realtime=v1\r\n"));
+        let received = server.received();
+        let sent: Vec<serde_json::Value> = received
+            .iter()
+            .filter_map(|message| match message {
+                Message::Text(text) => Some(serde_json::from_str(text).unwrap()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            sent[0],
+            json!({ "type": "session.update", "session": {
+                "modalities": ["text"],
+                "tools": [{ "type": "function", "name": "get_weather", "parameters": { "type": "object" } }]
+            } })
+        );
+        assert_eq!(
+            sent[1]["item"],
+            json!({ "type": "message", "role": "user", "content": [{ "type": "input_text", "text": "Weather in Paris?" }] })
+        );
+        assert_eq!(sent[2], json!({ "type": "response.create" }));
+        assert_eq!(
+            sent[3],
+            json!({ "type": "conversation.item.create", "item": { "type": "function_call_output", "call_id": "call_1", "output": "22C" } })
+        );
+        assert_eq!(sent[4], json!({ "type": "response.create" }));
+        assert!(received.contains(&Message::Pong(b"hb".to_vec())));
+        let close = Message::Close(Some(CloseFrame {
+            code: CloseCode::Normal,
+            reason: "".into(),
+        }));
+        assert_eq!(received.last(), Some(&close));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_realtime_server_close() -> Result<()> {
+        let frame = CloseFrame {
+            code: CloseCode::Error,
+            reason: "boom".into(),
+        };
+        let mut server = MockWebSocketServer::start(vec![vec![Message::Close(Some(frame.clone()))]]).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let mut session = sdk.realtime("gpt-4o-realtime-preview").await?;
+        let Some(Err(LlmError::WebSocket(msg))) = session.next().await else {
+            panic!("an abnormal close should end the stream with an error");
+        };
+        assert_eq!(msg, "closed with code 1011: boom");
+        assert!(session.next().await.is_none());
+        server.finished().await;
+        // the close was answered
+        assert_eq!(server.received(), [Message::Close(Some(frame))]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_realtime_handshake_rejected() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::new(
+            401,
+            r#"{"error": {"message": "Incorrect API key provided", "type": "invalid_request_error"}}"#,
+        )])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).token("sk-bad").build()?;
+        let err = sdk.realtime("gpt-4o-realtime-preview").await.unwrap_err();
+        assert!(err.is_auth_error());
+        assert_eq!(err.api_error().unwrap().message, "Incorrect API key provided");
+
+        let sdk = LLMSDK::builder()
+            .base_url(&server.url)
+            .proxy(reqwest::Proxy::all("http://127.0.0.1:1")?)
+            .build()?;
+        let err = sdk.realtime("gpt-4o-realtime-preview").await.unwrap_err();
+        assert!(matches!(err, LlmError::Validation(_)));
+        assert_eq!(server.requests().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_item_content_types() -> Result<()> {
+        let event: RealtimeServerEvent = serde_json::from_value(json!({
+            "type": "conversation.item.created",
+            "item": { "id": "item_5", "type": "message", "role": "user", "content": [
+                { "type": "item_reference", "id": "item_1" },
+                { "type": "input_image", "image_url": "data:image/png;base64,UE5H" },
+            ] }
+        }))?;
+        let RealtimeServerEvent::ConversationItemCreated {
+            item: RealtimeItem::Message { content, .. },
+            ..
+        } = event
+        else {
+            panic!("unexpected event: {event:?}");
+        };
+        assert_eq!(content, [RealtimeContent::ItemReference { id: "item_1".into() }, RealtimeContent::Other]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_audio_encodes_base64() -> Result<()> {
+        let event = RealtimeClientEvent::append_audio(&[0, 1, 2, 3]);
+        assert_eq!(
+            serde_json::to_value(&event)?,
+            json!({ "type": "input_audio_buffer.append", "audio": "AAECAw==" })
+        );
+        Ok(())
+    }
+}
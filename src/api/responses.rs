@@ -0,0 +1,916 @@
+use std::collections::HashMap;
+
+use futures_util::{stream::BoxStream, Stream, StreamExt};
+use reqwest::RequestBuilder;
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+
+use crate::{encode_path_segment, ApiError, AttributeFilter, IntoRequest, LlmError, RequestContext, Result, Tool};
+
+/// Creates a model response. Chain turns with `previous_response_id`, or with `Response::follow_up`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateResponseRequest {
+    /// ID of the model to use, e.g. gpt-4o or o3.
+    pub model: String,
+    /// Text or a list of input items to the model.
+    pub input: ResponseInput,
+    /// A system (or developer) message inserted into the model's context. Instructions of the
+    /// previous response are not carried over when chaining.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// The ID of the previous response, to continue a conversation from it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+    /// Whether to store the response so it can be retrieved and chained. Defaults to true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+    /// Whether to run the response in the background. Poll it with `LLMSDK::retrieve_response`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<bool>,
+    /// The tools the model may call, built-in or functions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ResponseTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ResponseToolChoice>,
+    /// Whether to allow the model to run tool calls in parallel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    /// Configuration options for reasoning models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ReasoningOptions>,
+    /// An upper bound for the number of tokens generated, including reasoning tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    /// If set, the response is streamed as server-sent events. Set by `LLMSDK::create_response_stream`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+impl CreateResponseRequest {
+    pub fn new(model: impl Into<String>, input: impl Into<ResponseInput>) -> Self {
+        CreateResponseRequest {
+            model: model.into(),
+            input: input.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for CreateResponseRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("responses").json(&self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ResponseInput {
+    Text(String),
+    Items(Vec<ResponseInputItem>),
+}
+
+impl Default for ResponseInput {
+    fn default() -> Self {
+        ResponseInput::Items(vec![])
+    }
+}
+
+impl From<String> for ResponseInput {
+    fn from(text: String) -> Self {
+        ResponseInput::Text(text)
+    }
+}
+
+impl From<&str> for ResponseInput {
+    fn from(text: &str) -> Self {
+        ResponseInput::Text(text.to_string())
+    }
+}
+
+impl From<Vec<ResponseInputItem>> for ResponseInput {
+    fn from(items: Vec<ResponseInputItem>) -> Self {
+        ResponseInput::Items(items)
+    }
+}
+
+/// An item of the model's context: a message, the result of a function call, or a reference to
+/// an item of a stored response.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseInputItem {
+    Message {
+        role: ResponseRole,
+        content: ResponseInputContent,
+    },
+    /// A function call of a previous response, needed when it was not stored.
+    FunctionCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
+    /// The output of a function call, sent back to the model.
+    FunctionCallOutput { call_id: String, output: String },
+    ItemReference { id: String },
+}
+
+impl ResponseInputItem {
+    pub fn message(role: ResponseRole, content: impl Into<ResponseInputContent>) -> Self {
+        ResponseInputItem::Message {
+            role,
+            content: content.into(),
+        }
+    }
+
+    pub fn user(content: impl Into<ResponseInputContent>) -> Self {
+        Self::message(ResponseRole::User, content)
+    }
+
+    pub fn developer(content: impl Into<ResponseInputContent>) -> Self {
+        Self::message(ResponseRole::Developer, content)
+    }
+
+    pub fn function_call_output(call_id: impl Into<String>, output: impl Into<String>) -> Self {
+        ResponseInputItem::FunctionCallOutput {
+            call_id: call_id.into(),
+            output: output.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseRole {
+    User,
+    Assistant,
+    System,
+    Developer,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ResponseInputContent {
+    Text(String),
+    Parts(Vec<ResponseInputPart>),
+}
+
+impl From<String> for ResponseInputContent {
+    fn from(text: String) -> Self {
+        ResponseInputContent::Text(text)
+    }
+}
+
+impl From<&str> for ResponseInputContent {
+    fn from(text: &str) -> Self {
+        ResponseInputContent::Text(text.to_string())
+    }
+}
+
+impl From<Vec<ResponseInputPart>> for ResponseInputContent {
+    fn from(parts: Vec<ResponseInputPart>) -> Self {
+        ResponseInputContent::Parts(parts)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseInputPart {
+    InputText {
+        text: String,
+    },
+    /// An image, by URL (or data URL) or by the ID of an uploaded file.
+    InputImage {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        image_url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        /// The detail level of the image: "auto", "low" or "high".
+        detail: String,
+    },
+    /// A file, by the ID of an uploaded file or inline as a base64 data URL.
+    InputFile {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filename: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_data: Option<String>,
+    },
+}
+
+impl ResponseInputPart {
+    pub fn text(text: impl Into<String>) -> Self {
+        ResponseInputPart::InputText { text: text.into() }
+    }
+
+    pub fn image_url(url: impl Into<String>) -> Self {
+        ResponseInputPart::InputImage {
+            image_url: Some(url.into()),
+            file_id: None,
+            detail: "auto".into(),
+        }
+    }
+
+    pub fn file(file_id: impl Into<String>) -> Self {
+        ResponseInputPart::InputFile {
+            file_id: Some(file_id.into()),
+            filename: None,
+            file_data: None,
+        }
+    }
+}
+
+/// A tool the model may use. Built-in tools run on OpenAI's side; function calls are returned
+/// to the caller as `ResponseOutputItem::FunctionCall`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseTool {
+    Function {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        parameters: serde_json::Value,
+        /// Whether to enforce strict parameter validation.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        strict: Option<bool>,
+    },
+    WebSearch {
+        /// How much context is retrieved from the web: "low", "medium" or "high".
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        search_context_size: Option<String>,
+    },
+    FileSearch {
+        vector_store_ids: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_num_results: Option<u32>,
+        /// Only search the files whose attributes match.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filters: Option<AttributeFilter>,
+    },
+    CodeInterpreter {
+        container: CodeInterpreterContainer,
+    },
+}
+
+impl ResponseTool {
+    pub fn web_search() -> Self {
+        ResponseTool::WebSearch {
+            search_context_size: None,
+        }
+    }
+
+    pub fn file_search(vector_store_ids: Vec<String>) -> Self {
+        ResponseTool::FileSearch {
+            vector_store_ids,
+            max_num_results: None,
+            filters: None,
+        }
+    }
+
+    /// The code interpreter, in a new container with the given files.
+    pub fn code_interpreter(file_ids: Vec<String>) -> Self {
+        ResponseTool::CodeInterpreter {
+            container: CodeInterpreterContainer::Auto(AutoContainer { file_ids }),
+        }
+    }
+}
+
+impl From<Tool> for ResponseTool {
+    fn from(tool: Tool) -> Self {
+        ResponseTool::Function {
+            name: tool.function.name,
+            description: tool.function.description,
+            parameters: tool.function.parameters,
+            strict: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CodeInterpreterContainer {
+    /// The ID of an existing container.
+    Id(String),
+    Auto(AutoContainer),
+}
+
+/// A container created for the request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "auto")]
+pub struct AutoContainer {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_ids: Vec<String>,
+}
+
+/// Controls which (if any) tool is called by the model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ResponseToolChoice {
+    None,
+    #[default]
+    Auto,
+    Required,
+    /// Forces the model to call the named function.
+    Function(String),
+}
+
+impl Serialize for ResponseToolChoice {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            ResponseToolChoice::None => serializer.serialize_str("none"),
+            ResponseToolChoice::Auto => serializer.serialize_str("auto"),
+            ResponseToolChoice::Required => serializer.serialize_str("required"),
+            ResponseToolChoice::Function(name) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("type", "function")?;
+                map.serialize_entry("name", name)?;
+                map.end()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ReasoningOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffort>,
+    /// Whether the model should return a summary of its reasoning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ReasoningSummary>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasoningEffort {
+    Minimal,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasoningSummary {
+    Auto,
+    Concise,
+    Detailed,
+}
+
+#[derive(Debug, Clone)]
+pub struct RetrieveResponseRequest {
+    pub id: String,
+}
+
+impl IntoRequest for RetrieveResponseRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("responses/{}", encode_path_segment(&self.id)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteResponseRequest {
+    pub id: String,
+}
+
+impl IntoRequest for DeleteResponseRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.delete(&format!("responses/{}", encode_path_segment(&self.id)))
+    }
+}
+
+/// Cancels a background response. Only responses created with `background` can be cancelled.
+#[derive(Debug, Clone)]
+pub struct CancelResponseRequest {
+    pub id: String,
+}
+
+impl IntoRequest for CancelResponseRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("responses/{}/cancel", encode_path_segment(&self.id)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Response {
+    pub id: String,
+    /// The object type, which is always "response".
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the response was created.
+    pub created_at: u64,
+    pub status: ResponseStatus,
+    pub model: String,
+    /// The items generated by the model, in order.
+    #[serde(default)]
+    pub output: Vec<ResponseOutputItem>,
+    /// Set when the response failed.
+    #[serde(default)]
+    pub error: Option<ApiError>,
+    #[serde(default)]
+    pub incomplete_details: Option<ResponseIncompleteDetails>,
+    #[serde(default)]
+    pub previous_response_id: Option<String>,
+    #[serde(default)]
+    pub background: Option<bool>,
+    #[serde(default)]
+    pub usage: Option<ResponseUsage>,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseStatus {
+    Queued,
+    InProgress,
+    Completed,
+    Failed,
+    Cancelled,
+    Incomplete,
+}
+
+impl ResponseStatus {
+    /// Whether the response has ended and its status will not change anymore.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, ResponseStatus::Queued | ResponseStatus::InProgress)
+    }
+}
+
+impl Response {
+    /// The text of every output message, concatenated.
+    pub fn output_text(&self) -> String {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                ResponseOutputItem::Message { content, .. } => Some(content),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|content| match content {
+                ResponseOutputContent::OutputText { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The function calls the model made, as `(call_id, name, arguments)`. Send their outputs
+    /// back with `ResponseInputItem::function_call_output`.
+    pub fn function_calls(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.output.iter().filter_map(|item| match item {
+            ResponseOutputItem::FunctionCall {
+                call_id,
+                name,
+                arguments,
+                ..
+            } => Some((call_id.as_str(), name.as_str(), arguments.as_str())),
+            _ => None,
+        })
+    }
+
+    /// The request for the next turn of the conversation: same model, chained to this response.
+    pub fn follow_up(&self, input: impl Into<ResponseInput>) -> CreateResponseRequest {
+        CreateResponseRequest {
+            previous_response_id: Some(self.id.clone()),
+            ..CreateResponseRequest::new(&self.model, input)
+        }
+    }
+
+    /// Drains an event stream and returns the final response it carries.
+    pub async fn from_stream(stream: impl Stream<Item = Result<ResponseStreamEvent>>) -> Result<Self> {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(event) = stream.next().await {
+            match event? {
+                ResponseStreamEvent::Completed { response }
+                | ResponseStreamEvent::Incomplete { response }
+                | ResponseStreamEvent::Failed { response } => return Ok(response),
+                ResponseStreamEvent::Error(error) => {
+                    let body = error.message.clone();
+                    return Err(LlmError::Api {
+                        status: reqwest::StatusCode::OK,
+                        error: Some(error),
+                        body,
+                    });
+                }
+                _ => {}
+            }
+        }
+        Err(LlmError::IncompleteStream("the stream ended before the response did".into()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ResponseIncompleteDetails {
+    /// "max_output_tokens" or "content_filter".
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct ResponseUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub total_tokens: u32,
+    #[serde(default)]
+    pub input_tokens_details: Option<ResponseInputTokensDetails>,
+    #[serde(default)]
+    pub output_tokens_details: Option<ResponseOutputTokensDetails>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct ResponseInputTokensDetails {
+    #[serde(default)]
+    pub cached_tokens: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct ResponseOutputTokensDetails {
+    #[serde(default)]
+    pub reasoning_tokens: u32,
+}
+
+/// An item generated by the model. Item types this SDK does not know are kept as `Other`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseOutputItem {
+    Message {
+        id: String,
+        role: ResponseRole,
+        #[serde(default)]
+        status: Option<String>,
+        content: Vec<ResponseOutputContent>,
+    },
+    FunctionCall {
+        #[serde(default)]
+        id: Option<String>,
+        call_id: String,
+        name: String,
+        /// The arguments of the call, as a JSON string.
+        arguments: String,
+        #[serde(default)]
+        status: Option<String>,
+    },
+    Reasoning {
+        id: String,
+        #[serde(default)]
+        summary: Vec<ReasoningSummaryText>,
+        #[serde(default)]
+        encrypted_content: Option<String>,
+    },
+    WebSearchCall {
+        id: String,
+        status: String,
+        /// What the search did, e.g. `{"type": "search", "query": "..."}`.
+        #[serde(default)]
+        action: Option<serde_json::Value>,
+    },
+    FileSearchCall {
+        id: String,
+        status: String,
+        #[serde(default)]
+        queries: Vec<String>,
+        #[serde(default)]
+        results: Option<Vec<serde_json::Value>>,
+    },
+    CodeInterpreterCall {
+        id: String,
+        status: String,
+        #[serde(default)]
+        code: Option<String>,
+        #[serde(default)]
+        container_id: Option<String>,
+        #[serde(default)]
+        outputs: Option<Vec<serde_json::Value>>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseOutputContent {
+    OutputText {
+        text: String,
+        /// Citations of files and URLs within the text, as returned by the API.
+        #[serde(default)]
+        annotations: Vec<serde_json::Value>,
+    },
+    Refusal {
+        refusal: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ReasoningSummaryText {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DeleteResponseResponse {
+    pub id: String,
+    /// The object type, which is always "response".
+    pub object: String,
+    pub deleted: bool,
+}
+
+/// A stream of response events, as returned by `LLMSDK::create_response_stream`.
+pub type ResponseStream = BoxStream<'static, Result<ResponseStreamEvent>>;
+
+/// A semantic event of a streamed response. Event types this SDK does not know are kept as `Other`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type")]
+pub enum ResponseStreamEvent {
+    #[serde(rename = "response.created")]
+    Created { response: Response },
+    #[serde(rename = "response.queued")]
+    Queued { response: Response },
+    #[serde(rename = "response.in_progress")]
+    InProgress { response: Response },
+    #[serde(rename = "response.completed")]
+    Completed { response: Response },
+    #[serde(rename = "response.incomplete")]
+    Incomplete { response: Response },
+    #[serde(rename = "response.failed")]
+    Failed { response: Response },
+    #[serde(rename = "response.output_item.added")]
+    OutputItemAdded { output_index: usize, item: ResponseOutputItem },
+    #[serde(rename = "response.output_item.done")]
+    OutputItemDone { output_index: usize, item: ResponseOutputItem },
+    #[serde(rename = "response.content_part.added")]
+    ContentPartAdded {
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        part: ResponseOutputContent,
+    },
+    #[serde(rename = "response.content_part.done")]
+    ContentPartDone {
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        part: ResponseOutputContent,
+    },
+    #[serde(rename = "response.output_text.delta")]
+    OutputTextDelta {
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        delta: String,
+    },
+    #[serde(rename = "response.output_text.done")]
+    OutputTextDone {
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        text: String,
+    },
+    #[serde(rename = "response.refusal.delta")]
+    RefusalDelta {
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        delta: String,
+    },
+    #[serde(rename = "response.refusal.done")]
+    RefusalDone {
+        item_id: String,
+        output_index: usize,
+        content_index: usize,
+        refusal: String,
+    },
+    #[serde(rename = "response.function_call_arguments.delta")]
+    FunctionCallArgumentsDelta {
+        item_id: String,
+        output_index: usize,
+        delta: String,
+    },
+    #[serde(rename = "response.function_call_arguments.done")]
+    FunctionCallArgumentsDone {
+        item_id: String,
+        output_index: usize,
+        arguments: String,
+    },
+    #[serde(rename = "response.reasoning_summary_text.delta")]
+    ReasoningSummaryTextDelta {
+        item_id: String,
+        output_index: usize,
+        summary_index: usize,
+        delta: String,
+    },
+    #[serde(rename = "response.reasoning_summary_text.done")]
+    ReasoningSummaryTextDone {
+        item_id: String,
+        output_index: usize,
+        summary_index: usize,
+        text: String,
+    },
+    /// The stream failed. No events follow.
+    #[serde(rename = "error")]
+    Error(ApiError),
+    #[serde(other)]
+    Other,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn response(id: &str, output: serde_json::Value) -> serde_json::Value {
+        json!({
+            "id": id,
+            "object": "response",
+            "created_at": 1741476542,
+            "status": "completed",
+            "model": "gpt-4o-2024-08-06",
+            "output": output,
+            "error": null,
+            "incomplete_details": null,
+            "previous_response_id": null,
+            "usage": {
+                "input_tokens": 36,
+                "input_tokens_details": { "cached_tokens": 0 },
+                "output_tokens": 87,
+                "output_tokens_details": { "reasoning_tokens": 64 },
+                "total_tokens": 123
+            },
+            "metadata": {}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_response_function_call_chain() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::json(response(
+                "resp_1",
+                json!([
+                    { "type": "reasoning", "id": "rs_1", "summary": [{ "type": "summary_text", "text": "Need the weather." }] },
+                    { "type": "web_search_call", "id": "ws_1", "status": "completed" },
+                    { "type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "get_weather",
+                      "arguments": "{\"city\":\"Paris\"}", "status": "completed" },
+                    { "type": "image_generation_call", "id": "ig_1", "status": "completed", "result": "..." }
+                ]),
+            )),
+            MockResponse::json(response(
+                "resp_2",
+                json!([{
+                    "type": "message", "id": "msg_1", "status": "completed", "role": "assistant",
+                    "content": [
+                        { "type": "output_text", "text": "It is 22C in Paris.", "annotations": [] },
+                        { "type": "output_audio", "data": "UklGRg==", "transcript": "It is 22C in Paris." }
+                    ]
+                }]),
+            )),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = CreateResponseRequest {
+            instructions: Some("Answer briefly.".into()),
+            tools: vec![
+                ResponseTool::web_search(),
+                ResponseTool::code_interpreter(vec![]),
+                Tool::function("get_weather", None, json!({ "type": "object" })).into(),
+            ],
+            reasoning: Some(ReasoningOptions {
+                effort: Some(ReasoningEffort::Low),
+                summary: Some(ReasoningSummary::Auto),
+            }),
+            ..CreateResponseRequest::new("o4-mini", "What is the weather in Paris?")
+        };
+        let res = sdk.create_response(req).await?;
+        assert_eq!(res.output.len(), 4);
+        assert_eq!(res.output[3], ResponseOutputItem::Other);
+        assert_eq!(res.usage.unwrap().output_tokens_details.unwrap().reasoning_tokens, 64);
+        let calls: Vec<_> = res.function_calls().collect();
+        assert_eq!(calls, [("call_1", "get_weather", "{\"city\":\"Paris\"}")]);
+
+        let next = res.follow_up(vec![ResponseInputItem::function_call_output("call_1", "22C")]);
+        let res = sdk.create_response(next).await?;
+        assert_eq!(res.output_text(), "It is 22C in Paris.");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/responses");
+        assert_eq!(
+            requests[0].json(),
+            json!({
+                "model": "o4-mini",
+                "input": "What is the weather in Paris?",
+                "instructions": "Answer briefly.",
+                "tools": [
+                    { "type": "web_search" },
+                    { "type": "code_interpreter", "container": { "type": "auto" } },
+                    { "type": "function", "name": "get_weather", "parameters": { "type": "object" } }
+                ],
+                "reasoning": { "effort": "low", "summary": "auto" }
+            })
+        );
+        assert_eq!(
+            requests[1].json(),
+            json!({
+                "model": "gpt-4o-2024-08-06",
+                "input": [{ "type": "function_call_output", "call_id": "call_1", "output": "22C" }],
+                "previous_response_id": "resp_1"
+            })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_response_stream_events() -> Result<()> {
+        let done = response(
+            "resp_1",
+            json!([{
+                "type": "message", "id": "msg_1", "status": "completed", "role": "assistant",
+                "content": [{ "type": "output_text", "text": "Hello", "annotations": [] }]
+            }]),
+        );
+        let mut created = done.clone();
+        created["status"] = json!("in_progress");
+        created["output"] = json!([]);
+        let events = [
+            json!({ "type": "response.created", "sequence_number": 0, "response": created }),
+            json!({ "type": "response.output_text.delta", "sequence_number": 1, "item_id": "msg_1",
+                    "output_index": 0, "content_index": 0, "delta": "Hel" }),
+            json!({ "type": "response.output_text.delta", "sequence_number": 2, "item_id": "msg_1",
+                    "output_index": 0, "content_index": 0, "delta": "lo" }),
+            json!({ "type": "response.output_text.annotation.added", "sequence_number": 3 }),
+            json!({ "type": "response.completed", "sequence_number": 4, "response": done }),
+        ];
+        let body: String = events.iter().map(|event| format!("event: {}\ndata: {event}\n\n", event["type"])).collect();
+        let server = MockServer::start(vec![MockResponse::new(200, body).header("content-type", "text/event-stream")]).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+
+        let events: Vec<_> = sdk
+            .create_response_stream(CreateResponseRequest::new("gpt-4o", "Say hello"))
+            .await?
+            .collect()
+            .await;
+        let deltas: String = events
+            .iter()
+            .filter_map(|event| match event {
+                Ok(ResponseStreamEvent::OutputTextDelta { delta, .. }) => Some(delta.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(deltas, "Hello");
+        assert!(matches!(events[3], Ok(ResponseStreamEvent::Other)));
+        let res = Response::from_stream(futures_util::stream::iter(events)).await?;
+        assert_eq!(res.status, ResponseStatus::Completed);
+        assert_eq!(res.output_text(), "Hello");
+        assert_eq!(server.requests()[0].json()["stream"], json!(true));
+
+        let error = json!({ "type": "error", "code": "server_error", "message": "The server had an error", "param": null });
+        let event: ResponseStreamEvent = serde_json::from_value(error)?;
+        let err = Response::from_stream(futures_util::stream::iter([Ok(event)])).await.unwrap_err();
+        assert_eq!(err.api_error().unwrap().code.as_deref(), Some("server_error"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_background_response_lifecycle() -> Result<()> {
+        let mut queued = response("resp_1", json!([]));
+        queued["status"] = json!("queued");
+        queued["background"] = json!(true);
+        let mut cancelled = queued.clone();
+        cancelled["status"] = json!("cancelled");
+        let server = MockServer::start(vec![
+            MockResponse::json(queued),
+            MockResponse::json(cancelled),
+            MockResponse::json(json!({ "id": "resp_1", "object": "response", "deleted": true })),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let res = sdk.retrieve_response("resp_1").await?;
+        assert!(!res.status.is_terminal());
+        assert_eq!(sdk.cancel_response("resp_1").await?.status, ResponseStatus::Cancelled);
+        assert!(sdk.delete_response("resp_1").await?.deleted);
+
+        assert_eq!(
+            server.calls(),
+            [
+                "GET /responses/resp_1",
+                "POST /responses/resp_1/cancel",
+                "DELETE /responses/resp_1",
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_create_response_live() -> Result<()> {
+        let sdk = LLMSDK::new(std::env::var("OPENAI_API_KEY")?);
+        let res = sdk.create_response(CreateResponseRequest::new("gpt-4o-mini", "Say hello in one word.")).await?;
+        let res = sdk.create_response(res.follow_up("And in French?")).await?;
+        println!("response: {}", res.output_text());
+        Ok(())
+    }
+}
@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use super::assistants::ASSISTANTS_BETA;
+use crate::{
+    encode_path_segment, AssistantTool, IntoRequest, MessageInput, PollOptions,
+    RequestContext, Result, ToolCall, ToolChoice, LLMSDK,
+};
+
+/// Runs an assistant on a thread.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateRunRequest {
+    #[serde(skip)]
+    pub thread_id: String,
+    /// The ID of the assistant to use to execute this run.
+    pub assistant_id: String,
+    /// Overrides the model of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Overrides the instructions of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Appended to the instructions of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_instructions: Option<String>,
+    /// Adds messages to the thread before creating the run.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub additional_messages: Vec<MessageInput>,
+    /// Overrides the tools of the assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<AssistantTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+    /// Whether to enable parallel function calling during tool use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// The maximum number of prompt tokens used over the course of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_prompt_tokens: Option<u32>,
+    /// The maximum number of completion tokens used over the course of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<u32>,
+}
+
+impl CreateRunRequest {
+    pub fn new(thread_id: impl Into<String>, assistant_id: impl Into<String>) -> Self {
+        CreateRunRequest {
+            thread_id: thread_id.into(),
+            assistant_id: assistant_id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for CreateRunRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("threads/{}/runs", encode_path_segment(&self.thread_id)))
+            .json(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetrieveRunRequest {
+    pub thread_id: String,
+    pub id: String,
+}
+
+impl IntoRequest for RetrieveRunRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&run_path(&self.thread_id, &self.id))
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+/// Cancels a run that is in progress.
+#[derive(Debug, Clone)]
+pub struct CancelRunRequest {
+    pub thread_id: String,
+    pub id: String,
+}
+
+impl IntoRequest for CancelRunRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("{}/cancel", run_path(&self.thread_id, &self.id)))
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+/// Sends the results of the tool calls a run requires. All outputs must be submitted in a
+/// single request.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubmitToolOutputsRequest {
+    #[serde(skip)]
+    pub thread_id: String,
+    #[serde(skip)]
+    pub run_id: String,
+    pub tool_outputs: Vec<ToolOutput>,
+}
+
+impl SubmitToolOutputsRequest {
+    pub fn new(thread_id: impl Into<String>, run_id: impl Into<String>, tool_outputs: Vec<ToolOutput>) -> Self {
+        SubmitToolOutputsRequest {
+            thread_id: thread_id.into(),
+            run_id: run_id.into(),
+            tool_outputs,
+        }
+    }
+}
+
+impl IntoRequest for SubmitToolOutputsRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("{}/submit_tool_outputs", run_path(&self.thread_id, &self.run_id)))
+            .json(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolOutput {
+    /// The ID of the tool call in `required_action` the output is for.
+    pub tool_call_id: String,
+    pub output: String,
+}
+
+impl ToolOutput {
+    pub fn new(tool_call_id: impl Into<String>, output: impl Into<String>) -> Self {
+        ToolOutput {
+            tool_call_id: tool_call_id.into(),
+            output: output.into(),
+        }
+    }
+}
+
+fn run_path(thread_id: &str, id: &str) -> String {
+    format!("threads/{}/runs/{}", encode_path_segment(thread_id), encode_path_segment(id))
+}
+
+/// An execution of an assistant on a thread.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Run {
+    pub id: String,
+    /// The object type, which is always "thread.run".
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the run was created.
+    pub created_at: u64,
+    pub thread_id: String,
+    pub assistant_id: String,
+    pub status: RunStatus,
+    /// What the run needs to continue. Set while the status is `requires_action`.
+    #[serde(default)]
+    pub required_action: Option<RequiredAction>,
+    #[serde(default)]
+    pub last_error: Option<RunError>,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub started_at: Option<u64>,
+    #[serde(default)]
+    pub cancelled_at: Option<u64>,
+    #[serde(default)]
+    pub failed_at: Option<u64>,
+    #[serde(default)]
+    pub completed_at: Option<u64>,
+    /// Why the run is incomplete.
+    #[serde(default)]
+    pub incomplete_details: Option<RunIncompleteDetails>,
+    pub model: String,
+    #[serde(default)]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub tools: Vec<AssistantTool>,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+    /// Usage statistics of the run, set once it is in a terminal status.
+    #[serde(default)]
+    pub usage: Option<RunUsage>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Queued,
+    InProgress,
+    RequiresAction,
+    Cancelling,
+    Cancelled,
+    Failed,
+    Completed,
+    Incomplete,
+    Expired,
+}
+
+impl RunStatus {
+    /// Whether the run has ended and its status will not change anymore.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            RunStatus::Cancelled | RunStatus::Failed | RunStatus::Completed | RunStatus::Incomplete | RunStatus::Expired
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RequiredAction {
+    /// The type of the action, which is always "submit_tool_outputs".
+    pub r#type: String,
+    pub submit_tool_outputs: SubmitToolOutputsAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SubmitToolOutputsAction {
+    pub tool_calls: Vec<ToolCall>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RunError {
+    /// One of "server_error", "rate_limit_exceeded" or "invalid_prompt".
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RunIncompleteDetails {
+    /// "max_completion_tokens" or "max_prompt_tokens".
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct RunUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+}
+
+/// Where `Run::wait` stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunOutcome {
+    /// The run waits for the outputs of these tool calls. Send them with `submit_tool_outputs`
+    /// and wait again.
+    RequiresAction { run: Run, tool_calls: Vec<ToolCall> },
+    /// The run is in a terminal status.
+    Finished(Run),
+}
+
+impl Run {
+    /// Polls the run until it requires action or ends.
+    pub async fn wait(&self, sdk: &LLMSDK, poll: PollOptions) -> Result<RunOutcome> {
+        let mut run = self.clone();
+        let mut interval = poll.initial_interval;
+        loop {
+            if run.status == RunStatus::RequiresAction {
+                let tool_calls = run
+                    .required_action
+                    .as_ref()
+                    .map(|action| action.submit_tool_outputs.tool_calls.clone())
+                    .unwrap_or_default();
+                return Ok(RunOutcome::RequiresAction { run, tool_calls });
+            }
+            if run.status.is_terminal() {
+                return Ok(RunOutcome::Finished(run));
+            }
+            tokio::time::sleep(interval).await;
+            let status = run.status;
+            run = sdk.retrieve_run(&run.thread_id, &run.id).await?;
+            interval = poll.next_interval(interval, run.status != status);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn run(status: &str) -> serde_json::Value {
+        let mut run = json!({
+            "id": "run_abc123",
+            "object": "thread.run",
+            "created_at": 1699063290,
+            "assistant_id": "asst_abc123",
+            "thread_id": "thread_abc123",
+            "status": status,
+            "started_at": 1699063290,
+            "expires_at": null,
+            "last_error": null,
+            "model": "gpt-4o",
+            "instructions": null,
+            "tools": [{ "type": "function", "function": { "name": "get_weather", "parameters": { "type": "object" } } }],
+            "metadata": {},
+            "usage": null
+        });
+        if status == "requires_action" {
+            run["required_action"] = json!({
+                "type": "submit_tool_outputs",
+                "submit_tool_outputs": {
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" }
+                    }]
+                }
+            });
+        }
+        if status == "completed" {
+            run["usage"] = json!({ "prompt_tokens": 100, "completion_tokens": 20, "total_tokens": 120 });
+        }
+        run
+    }
+
+    #[tokio::test]
+    async fn test_run_tool_call_round_trip() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::json(run("queued")),
+            MockResponse::json(run("in_progress")),
+            MockResponse::json(run("requires_action")),
+            MockResponse::json(run("queued")),
+            MockResponse::json(run("completed")),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let poll = PollOptions::default().with_interval(Duration::from_millis(1), Duration::from_millis(5));
+
+        let run = sdk.create_run(CreateRunRequest::new("thread_abc123", "asst_abc123")).await?;
+        let RunOutcome::RequiresAction { run, tool_calls } = run.wait(&sdk, poll).await? else {
+            panic!("the run should require action");
+        };
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].function.name, "get_weather");
+        let outputs = vec![ToolOutput::new(&tool_calls[0].id, "22C")];
+        let run = sdk
+            .submit_tool_outputs(SubmitToolOutputsRequest::new(&run.thread_id, &run.id, outputs))
+            .await?;
+        let RunOutcome::Finished(run) = run.wait(&sdk, poll).await? else {
+            panic!("the run should be finished");
+        };
+        assert_eq!(run.status, RunStatus::Completed);
+        assert_eq!(run.usage.unwrap().total_tokens, 120);
+
+        let requests = server.requests();
+        assert!(requests.iter().all(|req| req.header("openai-beta") == Some("assistants=v2")));
+        assert_eq!(
+            server.calls(),
+            [
+                "POST /threads/thread_abc123/runs",
+                "GET /threads/thread_abc123/runs/run_abc123",
+                "GET /threads/thread_abc123/runs/run_abc123",
+                "POST /threads/thread_abc123/runs/run_abc123/submit_tool_outputs",
+                "GET /threads/thread_abc123/runs/run_abc123",
+            ]
+        );
+        assert_eq!(requests[0].json(), json!({ "assistant_id": "asst_abc123" }));
+        assert_eq!(
+            requests[3].json(),
+            json!({ "tool_outputs": [{ "tool_call_id": "call_1", "output": "22C" }] })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_beta_header_from_context_wins() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(run("cancelling"))]).await;
+        let sdk = LLMSDK::builder()
+            .base_url(&server.url)
+            .header("OpenAI-Beta", "assistants=v1")
+            .build()?;
+        sdk.cancel_run("thread_abc123", "run_abc123").await?;
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/threads/thread_abc123/runs/run_abc123/cancel");
+        let betas: Vec<_> = req.headers.iter().filter(|(name, _)| name.eq_ignore_ascii_case("openai-beta")).collect();
+        assert_eq!(betas.len(), 1);
+        assert_eq!(req.header("openai-beta"), Some("assistants=v1"));
+        Ok(())
+    }
+}
@@ -0,0 +1,504 @@
+use std::collections::HashMap;
+
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use super::assistants::ASSISTANTS_BETA;
+use crate::{
+    encode_path_segment, AssistantTool, IntoRequest, ListOrder, ListResponse,
+    RequestContext, ToolResources,
+};
+
+/// Creates a thread, optionally with its first messages.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateThreadRequest {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<MessageInput>,
+    /// Resources made available to the assistant's tools in this thread.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_resources: Option<ToolResources>,
+    /// Set of 16 key-value pairs that can be attached to the thread.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+impl CreateThreadRequest {
+    pub fn new(messages: Vec<MessageInput>) -> Self {
+        CreateThreadRequest {
+            messages,
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for CreateThreadRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("threads").json(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+/// Modifies a thread. Only the fields that are set are changed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModifyThreadRequest {
+    #[serde(skip)]
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_resources: Option<ToolResources>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl ModifyThreadRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        ModifyThreadRequest {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for ModifyThreadRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("threads/{}", encode_path_segment(&self.id)))
+            .json(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetrieveThreadRequest {
+    pub id: String,
+}
+
+impl IntoRequest for RetrieveThreadRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("threads/{}", encode_path_segment(&self.id)))
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteThreadRequest {
+    pub id: String,
+}
+
+impl IntoRequest for DeleteThreadRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.delete(&format!("threads/{}", encode_path_segment(&self.id)))
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+/// A message to add to a thread.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MessageInput {
+    pub role: MessageRole,
+    pub content: MessageInputContent,
+    /// Files attached to the message, and the tools they should be added to.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<MessageAttachment>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+impl MessageInput {
+    pub fn new(role: MessageRole, content: impl Into<MessageInputContent>) -> Self {
+        MessageInput {
+            role,
+            content: content.into(),
+            attachments: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn user(content: impl Into<MessageInputContent>) -> Self {
+        Self::new(MessageRole::User, content)
+    }
+
+    pub fn assistant(content: impl Into<MessageInputContent>) -> Self {
+        Self::new(MessageRole::Assistant, content)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageRole {
+    User,
+    Assistant,
+}
+
+/// The content of a message: either plain text or a list of text and image parts.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum MessageInputContent {
+    Text(String),
+    Parts(Vec<MessageContentPart>),
+}
+
+impl From<String> for MessageInputContent {
+    fn from(text: String) -> Self {
+        MessageInputContent::Text(text)
+    }
+}
+
+impl From<&str> for MessageInputContent {
+    fn from(text: &str) -> Self {
+        MessageInputContent::Text(text.to_string())
+    }
+}
+
+impl From<Vec<MessageContentPart>> for MessageInputContent {
+    fn from(parts: Vec<MessageContentPart>) -> Self {
+        MessageInputContent::Parts(parts)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageContentPart {
+    Text { text: String },
+    ImageFile { image_file: ImageFile },
+    ImageUrl { image_url: ImageUrl },
+}
+
+impl MessageContentPart {
+    pub fn text(text: impl Into<String>) -> Self {
+        MessageContentPart::Text { text: text.into() }
+    }
+
+    pub fn image_file(file_id: impl Into<String>) -> Self {
+        MessageContentPart::ImageFile {
+            image_file: ImageFile {
+                file_id: file_id.into(),
+                detail: None,
+            },
+        }
+    }
+
+    pub fn image_url(url: impl Into<String>) -> Self {
+        MessageContentPart::ImageUrl {
+            image_url: ImageUrl {
+                url: url.into(),
+                detail: None,
+            },
+        }
+    }
+}
+
+/// An image uploaded through the files API with the `vision` purpose.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageFile {
+    pub file_id: String,
+    /// The detail level of the image: "auto", "low" or "high".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageUrl {
+    pub url: String,
+    /// The detail level of the image: "auto", "low" or "high".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageAttachment {
+    pub file_id: String,
+    /// The tools to add the file to: code_interpreter and/or file_search.
+    pub tools: Vec<AssistantTool>,
+}
+
+/// Creates a message in a thread.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateMessageRequest {
+    #[serde(skip)]
+    pub thread_id: String,
+    #[serde(flatten)]
+    pub message: MessageInput,
+}
+
+impl CreateMessageRequest {
+    pub fn new(thread_id: impl Into<String>, message: MessageInput) -> Self {
+        CreateMessageRequest {
+            thread_id: thread_id.into(),
+            message,
+        }
+    }
+}
+
+impl IntoRequest for CreateMessageRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("threads/{}/messages", encode_path_segment(&self.thread_id)))
+            .json(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+/// Returns the messages of a thread.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListMessagesRequest {
+    #[serde(skip)]
+    pub thread_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// A limit on the number of objects to be returned, between 1 and 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<ListOrder>,
+    /// Only return the messages generated by this run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+}
+
+impl ListMessagesRequest {
+    pub fn new(thread_id: impl Into<String>) -> Self {
+        ListMessagesRequest {
+            thread_id: thread_id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for ListMessagesRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("threads/{}/messages", encode_path_segment(&self.thread_id)))
+            .query(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetrieveMessageRequest {
+    pub thread_id: String,
+    pub id: String,
+}
+
+impl IntoRequest for RetrieveMessageRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&message_path(&self.thread_id, &self.id))
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+/// Modifies the metadata of a message.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModifyMessageRequest {
+    #[serde(skip)]
+    pub thread_id: String,
+    #[serde(skip)]
+    pub id: String,
+    pub metadata: HashMap<String, String>,
+}
+
+impl IntoRequest for ModifyMessageRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&message_path(&self.thread_id, &self.id)).json(&self)
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteMessageRequest {
+    pub thread_id: String,
+    pub id: String,
+}
+
+impl IntoRequest for DeleteMessageRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.delete(&message_path(&self.thread_id, &self.id))
+    }
+
+    fn beta(&self) -> Option<&'static str> {
+        Some(ASSISTANTS_BETA)
+    }
+}
+
+fn message_path(thread_id: &str, id: &str) -> String {
+    format!(
+        "threads/{}/messages/{}",
+        encode_path_segment(thread_id),
+        encode_path_segment(id)
+    )
+}
+
+/// A conversation between an assistant and a user.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Thread {
+    pub id: String,
+    /// The object type, which is always "thread".
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the thread was created.
+    pub created_at: u64,
+    #[serde(default)]
+    pub tool_resources: Option<ToolResources>,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// A message within a thread.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ThreadMessage {
+    pub id: String,
+    /// The object type, which is always "thread.message".
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the message was created.
+    pub created_at: u64,
+    pub thread_id: String,
+    /// The status of the message: "in_progress", "incomplete" or "completed".
+    #[serde(default)]
+    pub status: Option<String>,
+    pub role: MessageRole,
+    pub content: Vec<MessageContent>,
+    /// The assistant that authored the message, if any.
+    #[serde(default)]
+    pub assistant_id: Option<String>,
+    /// The run that authored the message, if any.
+    #[serde(default)]
+    pub run_id: Option<String>,
+    #[serde(default)]
+    pub attachments: Option<Vec<MessageAttachment>>,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl ThreadMessage {
+    /// The text parts of the message, joined by newlines.
+    pub fn text(&self) -> String {
+        let texts: Vec<_> = self
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                MessageContent::Text { text } => Some(text.value.as_str()),
+                _ => None,
+            })
+            .collect();
+        texts.join("\n")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageContent {
+    Text { text: MessageText },
+    ImageFile { image_file: ImageFile },
+    ImageUrl { image_url: ImageUrl },
+    Refusal { refusal: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MessageText {
+    pub value: String,
+    /// File citations and file paths within the text, as returned by the API.
+    #[serde(default)]
+    pub annotations: Vec<serde_json::Value>,
+}
+
+pub type ListMessagesResponse = ListResponse<ThreadMessage>;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn message(id: &str, text: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "object": "thread.message",
+            "created_at": 1699017614,
+            "assistant_id": null,
+            "thread_id": "thread_abc123",
+            "run_id": null,
+            "role": "user",
+            "content": [{ "type": "text", "text": { "value": text, "annotations": [] } }],
+            "attachments": [],
+            "metadata": {}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_threads_and_messages() -> Result<()> {
+        let thread = json!({ "id": "thread_abc123", "object": "thread", "created_at": 1699012949, "metadata": {}, "tool_resources": {} });
+        let server = MockServer::start(vec![
+            MockResponse::json(thread),
+            MockResponse::json(message("msg_2", "And this image?")),
+            MockResponse::json(json!({ "object": "list", "data": [message("msg_2", "And this image?"), message("msg_1", "Hi")], "has_more": false })),
+            MockResponse::json(json!({ "id": "thread_abc123", "object": "thread.deleted", "deleted": true })),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let thread = sdk.create_thread(CreateThreadRequest::new(vec![MessageInput::user("Hi")])).await?;
+        let content = vec![
+            MessageContentPart::text("And this image?"),
+            MessageContentPart::image_file("file-img"),
+        ];
+        let message = sdk
+            .create_message(CreateMessageRequest::new(&thread.id, MessageInput::user(content)))
+            .await?;
+        assert_eq!(message.text(), "And this image?");
+        let req = ListMessagesRequest {
+            order: Some(ListOrder::Desc),
+            ..ListMessagesRequest::new(&thread.id)
+        };
+        assert_eq!(sdk.list_messages(req).await?.data[1].text(), "Hi");
+        assert!(sdk.delete_thread(&thread.id).await?.deleted);
+
+        let requests = server.requests();
+        assert!(requests.iter().all(|req| req.header("openai-beta") == Some("assistants=v2")));
+        assert_eq!(
+            server.calls(),
+            [
+                "POST /threads",
+                "POST /threads/thread_abc123/messages",
+                "GET /threads/thread_abc123/messages?order=desc",
+                "DELETE /threads/thread_abc123",
+            ]
+        );
+        assert_eq!(requests[0].json(), json!({ "messages": [{ "role": "user", "content": "Hi" }] }));
+        assert_eq!(
+            requests[1].json(),
+            json!({
+                "role": "user",
+                "content": [
+                    { "type": "text", "text": "And this image?" },
+                    { "type": "image_file", "image_file": { "file_id": "file-img" } }
+                ]
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,895 @@
+use std::collections::HashMap;
+
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    encode_path_segment, IntoRequest, ListOrder, ListResponse, LlmError, PollOptions, RequestContext, Result, LLMSDK,
+};
+
+/// Creates a vector store, optionally with files to index right away.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateVectorStoreRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Files to add to the vector store.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub file_ids: Vec<String>,
+    /// How `file_ids` are chunked. Defaults to `auto`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunking_strategy: Option<ChunkingStrategy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<VectorStoreExpiration>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+impl CreateVectorStoreRequest {
+    pub fn new(name: impl Into<String>) -> Self {
+        CreateVectorStoreRequest {
+            name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        validate_chunking(self.chunking_strategy.as_ref())
+    }
+}
+
+impl IntoRequest for CreateVectorStoreRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post("vector_stores").json(&self)
+    }
+}
+
+/// Modifies a vector store. Only the fields that are set are changed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModifyVectorStoreRequest {
+    #[serde(skip)]
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<VectorStoreExpiration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+impl ModifyVectorStoreRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        ModifyVectorStoreRequest {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for ModifyVectorStoreRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("vector_stores/{}", encode_path_segment(&self.id)))
+            .json(&self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetrieveVectorStoreRequest {
+    pub id: String,
+}
+
+impl IntoRequest for RetrieveVectorStoreRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("vector_stores/{}", encode_path_segment(&self.id)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteVectorStoreRequest {
+    pub id: String,
+}
+
+impl IntoRequest for DeleteVectorStoreRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.delete(&format!("vector_stores/{}", encode_path_segment(&self.id)))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListVectorStoresRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// A limit on the number of objects to be returned, between 1 and 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<ListOrder>,
+}
+
+impl IntoRequest for ListVectorStoresRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get("vector_stores").query(&self)
+    }
+}
+
+/// How files are split into chunks before they are embedded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChunkingStrategy {
+    /// Chunks of 800 tokens with an overlap of 400 tokens.
+    #[default]
+    Auto,
+    Static {
+        r#static: StaticChunking,
+    },
+    /// The strategy of files indexed before chunking strategies were introduced.
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaticChunking {
+    /// Between 100 and 4096.
+    pub max_chunk_size_tokens: u32,
+    /// At most half of `max_chunk_size_tokens`.
+    pub chunk_overlap_tokens: u32,
+}
+
+impl ChunkingStrategy {
+    pub fn fixed(max_chunk_size_tokens: u32, chunk_overlap_tokens: u32) -> Self {
+        ChunkingStrategy::Static {
+            r#static: StaticChunking {
+                max_chunk_size_tokens,
+                chunk_overlap_tokens,
+            },
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ChunkingStrategy::Static { r#static } => {
+                if !(100..=4096).contains(&r#static.max_chunk_size_tokens) {
+                    return Err(LlmError::Validation(format!(
+                        "max_chunk_size_tokens must be between 100 and 4096, got {}",
+                        r#static.max_chunk_size_tokens
+                    )));
+                }
+                if r#static.chunk_overlap_tokens > r#static.max_chunk_size_tokens / 2 {
+                    return Err(LlmError::Validation(format!(
+                        "chunk_overlap_tokens must not exceed half of max_chunk_size_tokens ({}), got {}",
+                        r#static.max_chunk_size_tokens / 2,
+                        r#static.chunk_overlap_tokens
+                    )));
+                }
+                Ok(())
+            }
+            ChunkingStrategy::Other => Err(LlmError::Validation("the `other` chunking strategy cannot be requested".into())),
+            ChunkingStrategy::Auto => Ok(()),
+        }
+    }
+}
+
+fn validate_chunking(strategy: Option<&ChunkingStrategy>) -> Result<()> {
+    strategy.map_or(Ok(()), ChunkingStrategy::validate)
+}
+
+/// Expires the vector store a number of days after it was last used.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorStoreExpiration {
+    /// The anchor timestamp, which is always "last_active_at".
+    pub anchor: String,
+    pub days: u32,
+}
+
+impl VectorStoreExpiration {
+    pub fn days_after_last_active(days: u32) -> Self {
+        VectorStoreExpiration {
+            anchor: "last_active_at".into(),
+            days,
+        }
+    }
+}
+
+/// A collection of processed files that can be searched by the file_search tool.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VectorStore {
+    pub id: String,
+    /// The object type, which is always "vector_store".
+    pub object: String,
+    /// The Unix timestamp (in seconds) for when the vector store was created.
+    pub created_at: u64,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The total number of bytes used by the files in the vector store.
+    #[serde(default)]
+    pub usage_bytes: u64,
+    pub file_counts: VectorStoreFileCounts,
+    pub status: VectorStoreStatus,
+    #[serde(default)]
+    pub expires_after: Option<VectorStoreExpiration>,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub last_active_at: Option<u64>,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorStoreStatus {
+    Expired,
+    InProgress,
+    Completed,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct VectorStoreFileCounts {
+    pub in_progress: u32,
+    pub completed: u32,
+    pub failed: u32,
+    pub cancelled: u32,
+    pub total: u32,
+}
+
+pub type ListVectorStoresResponse = ListResponse<VectorStore>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DeleteVectorStoreResponse {
+    pub id: String,
+    /// "vector_store.deleted" or "vector_store.file.deleted".
+    pub object: String,
+    pub deleted: bool,
+}
+
+/// Attaches an uploaded file to a vector store, which starts indexing it.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateVectorStoreFileRequest {
+    #[serde(skip)]
+    pub vector_store_id: String,
+    pub file_id: String,
+    /// Key-value pairs stored with the file, used by search filters. Up to 16 keys.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub attributes: HashMap<String, AttributeValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunking_strategy: Option<ChunkingStrategy>,
+}
+
+impl CreateVectorStoreFileRequest {
+    pub fn new(vector_store_id: impl Into<String>, file_id: impl Into<String>) -> Self {
+        CreateVectorStoreFileRequest {
+            vector_store_id: vector_store_id.into(),
+            file_id: file_id.into(),
+            attributes: HashMap::new(),
+            chunking_strategy: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        validate_chunking(self.chunking_strategy.as_ref())
+    }
+}
+
+impl IntoRequest for CreateVectorStoreFileRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("vector_stores/{}/files", encode_path_segment(&self.vector_store_id)))
+            .json(&self)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListVectorStoreFilesRequest {
+    #[serde(skip)]
+    pub vector_store_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<ListOrder>,
+    /// Only return files with this status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<VectorStoreFileStatus>,
+}
+
+impl ListVectorStoreFilesRequest {
+    pub fn new(vector_store_id: impl Into<String>) -> Self {
+        ListVectorStoreFilesRequest {
+            vector_store_id: vector_store_id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoRequest for ListVectorStoreFilesRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&format!("vector_stores/{}/files", encode_path_segment(&self.vector_store_id)))
+            .query(&self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetrieveVectorStoreFileRequest {
+    pub vector_store_id: String,
+    pub file_id: String,
+}
+
+impl IntoRequest for RetrieveVectorStoreFileRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&vector_store_path(&self.vector_store_id, "files", &self.file_id))
+    }
+}
+
+/// Removes a file from a vector store. The file itself is not deleted.
+#[derive(Debug, Clone)]
+pub struct DeleteVectorStoreFileRequest {
+    pub vector_store_id: String,
+    pub file_id: String,
+}
+
+impl IntoRequest for DeleteVectorStoreFileRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.delete(&vector_store_path(&self.vector_store_id, "files", &self.file_id))
+    }
+}
+
+fn vector_store_path(vector_store_id: &str, kind: &str, id: &str) -> String {
+    format!(
+        "vector_stores/{}/{kind}/{}",
+        encode_path_segment(vector_store_id),
+        encode_path_segment(id)
+    )
+}
+
+/// A file attached to a vector store.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VectorStoreFile {
+    /// The ID of the file, as returned by the files API.
+    pub id: String,
+    /// The object type, which is always "vector_store.file".
+    pub object: String,
+    pub created_at: u64,
+    pub vector_store_id: String,
+    pub status: VectorStoreFileStatus,
+    /// The size of the file in the vector store, which may differ from its original size.
+    #[serde(default)]
+    pub usage_bytes: u64,
+    /// Why indexing failed, when the status is `failed`.
+    #[serde(default)]
+    pub last_error: Option<VectorStoreFileError>,
+    #[serde(default)]
+    pub chunking_strategy: Option<ChunkingStrategy>,
+    #[serde(default)]
+    pub attributes: Option<HashMap<String, AttributeValue>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorStoreFileStatus {
+    InProgress,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct VectorStoreFileError {
+    /// One of "server_error", "unsupported_file" or "invalid_file".
+    pub code: String,
+    pub message: String,
+}
+
+pub type ListVectorStoreFilesResponse = ListResponse<VectorStoreFile>;
+
+/// Attaches several files to a vector store at once.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateVectorStoreFileBatchRequest {
+    #[serde(skip)]
+    pub vector_store_id: String,
+    pub file_ids: Vec<String>,
+    /// Attributes stored with every file of the batch.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub attributes: HashMap<String, AttributeValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunking_strategy: Option<ChunkingStrategy>,
+}
+
+impl CreateVectorStoreFileBatchRequest {
+    pub fn new(vector_store_id: impl Into<String>, file_ids: Vec<String>) -> Self {
+        CreateVectorStoreFileBatchRequest {
+            vector_store_id: vector_store_id.into(),
+            file_ids,
+            attributes: HashMap::new(),
+            chunking_strategy: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.file_ids.is_empty() {
+            return Err(LlmError::Validation("a file batch needs at least one file".into()));
+        }
+        validate_chunking(self.chunking_strategy.as_ref())
+    }
+}
+
+impl IntoRequest for CreateVectorStoreFileBatchRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!(
+            "vector_stores/{}/file_batches",
+            encode_path_segment(&self.vector_store_id)
+        ))
+        .json(&self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetrieveVectorStoreFileBatchRequest {
+    pub vector_store_id: String,
+    pub batch_id: String,
+}
+
+impl IntoRequest for RetrieveVectorStoreFileBatchRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.get(&vector_store_path(&self.vector_store_id, "file_batches", &self.batch_id))
+    }
+}
+
+/// Cancels a file batch, stopping the indexing of its files as soon as possible.
+#[derive(Debug, Clone)]
+pub struct CancelVectorStoreFileBatchRequest {
+    pub vector_store_id: String,
+    pub batch_id: String,
+}
+
+impl IntoRequest for CancelVectorStoreFileBatchRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!(
+            "{}/cancel",
+            vector_store_path(&self.vector_store_id, "file_batches", &self.batch_id)
+        ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VectorStoreFileBatch {
+    pub id: String,
+    /// The object type, which is always "vector_store.files_batch".
+    pub object: String,
+    pub created_at: u64,
+    pub vector_store_id: String,
+    /// Uses the same statuses as single files.
+    pub status: VectorStoreFileStatus,
+    pub file_counts: VectorStoreFileCounts,
+}
+
+impl VectorStoreFile {
+    /// Polls the file until it is indexed, or indexing failed or was cancelled.
+    pub async fn wait(&self, sdk: &LLMSDK, poll: PollOptions) -> Result<VectorStoreFile> {
+        let mut file = self.clone();
+        let mut interval = poll.initial_interval;
+        while file.status == VectorStoreFileStatus::InProgress {
+            tokio::time::sleep(interval).await;
+            let status = file.status;
+            file = sdk.retrieve_vector_store_file(&file.vector_store_id, &file.id).await?;
+            interval = poll.next_interval(interval, file.status != status);
+        }
+        Ok(file)
+    }
+}
+
+impl VectorStoreFileBatch {
+    /// Polls the batch until every file is processed or the batch is cancelled. Files that
+    /// failed are counted in `file_counts.failed`.
+    pub async fn wait(&self, sdk: &LLMSDK, poll: PollOptions) -> Result<VectorStoreFileBatch> {
+        let mut batch = self.clone();
+        let mut interval = poll.initial_interval;
+        while batch.status == VectorStoreFileStatus::InProgress {
+            tokio::time::sleep(interval).await;
+            let counts = batch.file_counts;
+            batch = sdk
+                .retrieve_vector_store_file_batch(&batch.vector_store_id, &batch.id)
+                .await?;
+            interval = poll.next_interval(interval, batch.file_counts != counts);
+        }
+        Ok(batch)
+    }
+}
+
+/// The value of a file attribute.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    String(String),
+    Number(f64),
+    Bool(bool),
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        AttributeValue::String(value)
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        AttributeValue::String(value.to_string())
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        AttributeValue::Number(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        AttributeValue::Number(value as f64)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Bool(value)
+    }
+}
+
+/// A filter on file attributes: a comparison of one attribute, or a combination of filters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttributeFilter {
+    Eq { key: String, value: AttributeValue },
+    Ne { key: String, value: AttributeValue },
+    Gt { key: String, value: AttributeValue },
+    Gte { key: String, value: AttributeValue },
+    Lt { key: String, value: AttributeValue },
+    Lte { key: String, value: AttributeValue },
+    And { filters: Vec<AttributeFilter> },
+    Or { filters: Vec<AttributeFilter> },
+}
+
+impl AttributeFilter {
+    pub fn eq(key: impl Into<String>, value: impl Into<AttributeValue>) -> Self {
+        AttributeFilter::Eq {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn ne(key: impl Into<String>, value: impl Into<AttributeValue>) -> Self {
+        AttributeFilter::Ne {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn gt(key: impl Into<String>, value: impl Into<AttributeValue>) -> Self {
+        AttributeFilter::Gt {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn gte(key: impl Into<String>, value: impl Into<AttributeValue>) -> Self {
+        AttributeFilter::Gte {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn lt(key: impl Into<String>, value: impl Into<AttributeValue>) -> Self {
+        AttributeFilter::Lt {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn lte(key: impl Into<String>, value: impl Into<AttributeValue>) -> Self {
+        AttributeFilter::Lte {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn and(filters: Vec<AttributeFilter>) -> Self {
+        AttributeFilter::And { filters }
+    }
+
+    pub fn or(filters: Vec<AttributeFilter>) -> Self {
+        AttributeFilter::Or { filters }
+    }
+}
+
+/// Searches the chunks of a vector store that are the most relevant to a query.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchVectorStoreRequest {
+    #[serde(skip)]
+    pub vector_store_id: String,
+    pub query: SearchQuery,
+    /// Only search the files whose attributes match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<AttributeFilter>,
+    /// The maximum number of results to return, between 1 and 50. Defaults to 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_num_results: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_options: Option<RankingOptions>,
+    /// Whether to rewrite the natural language query for vector search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rewrite_query: Option<bool>,
+}
+
+impl SearchVectorStoreRequest {
+    pub fn new(vector_store_id: impl Into<String>, query: impl Into<SearchQuery>) -> Self {
+        SearchVectorStoreRequest {
+            vector_store_id: vector_store_id.into(),
+            query: query.into(),
+            filters: None,
+            max_num_results: None,
+            ranking_options: None,
+            rewrite_query: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        match self.max_num_results {
+            Some(n) if !(1..=50).contains(&n) => Err(LlmError::Validation(format!(
+                "max_num_results must be between 1 and 50, got {n}"
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl IntoRequest for SearchVectorStoreRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&format!("vector_stores/{}/search", encode_path_segment(&self.vector_store_id)))
+            .json(&self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum SearchQuery {
+    Text(String),
+    Texts(Vec<String>),
+}
+
+impl From<String> for SearchQuery {
+    fn from(query: String) -> Self {
+        SearchQuery::Text(query)
+    }
+}
+
+impl From<&str> for SearchQuery {
+    fn from(query: &str) -> Self {
+        SearchQuery::Text(query.to_string())
+    }
+}
+
+impl From<Vec<String>> for SearchQuery {
+    fn from(queries: Vec<String>) -> Self {
+        SearchQuery::Texts(queries)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RankingOptions {
+    /// "auto" or a specific ranker, e.g. "default-2024-11-15".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranker: Option<String>,
+    /// Results scoring below the threshold, between 0 and 1, are dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_threshold: Option<f64>,
+}
+
+/// One page of search results.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SearchVectorStoreResponse {
+    /// The object type, which is always "vector_store.search_results.page".
+    pub object: String,
+    /// The queries that were searched, after rewriting.
+    #[serde(default)]
+    pub search_query: Vec<String>,
+    pub data: Vec<VectorStoreSearchResult>,
+    #[serde(default)]
+    pub has_more: bool,
+    #[serde(default)]
+    pub next_page: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VectorStoreSearchResult {
+    pub file_id: String,
+    pub filename: String,
+    /// The similarity score of the result, between 0 and 1.
+    pub score: f64,
+    #[serde(default)]
+    pub attributes: Option<HashMap<String, AttributeValue>>,
+    /// The matching chunks of the file.
+    pub content: Vec<SearchResultContent>,
+}
+
+impl VectorStoreSearchResult {
+    /// The text of the matching chunks, joined by newlines.
+    pub fn text(&self) -> String {
+        let texts: Vec<_> = self.content.iter().map(|content| content.text.as_str()).collect();
+        texts.join("\n")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SearchResultContent {
+    /// The type of the content, which is always "text".
+    pub r#type: String,
+    pub text: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        mock::{MockResponse, MockServer},
+        LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    fn vector_store_file(status: &str) -> serde_json::Value {
+        json!({
+            "id": "file-abc123",
+            "object": "vector_store.file",
+            "created_at": 1699061776,
+            "usage_bytes": 1234,
+            "vector_store_id": "vs_abc123",
+            "status": status,
+            "last_error": null,
+            "chunking_strategy": { "type": "static", "static": { "max_chunk_size_tokens": 400, "chunk_overlap_tokens": 100 } },
+            "attributes": { "author": "Ada", "year": 2024 }
+        })
+    }
+
+    fn file_batch(status: &str, completed: u32) -> serde_json::Value {
+        json!({
+            "id": "vsfb_abc123",
+            "object": "vector_store.files_batch",
+            "created_at": 1699061776,
+            "vector_store_id": "vs_abc123",
+            "status": status,
+            "file_counts": { "in_progress": 2 - completed, "completed": completed, "failed": 0, "cancelled": 0, "total": 2 }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_attach_files_and_wait() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::json(json!({
+                "id": "vs_abc123",
+                "object": "vector_store",
+                "created_at": 1699061776,
+                "name": "Support FAQ",
+                "usage_bytes": 0,
+                "file_counts": { "in_progress": 0, "completed": 0, "failed": 0, "cancelled": 0, "total": 0 },
+                "status": "completed",
+                "expires_after": { "anchor": "last_active_at", "days": 7 },
+                "last_active_at": 1699061776,
+                "metadata": {}
+            })),
+            MockResponse::json(vector_store_file("in_progress")),
+            MockResponse::json(vector_store_file("in_progress")),
+            MockResponse::json(vector_store_file("completed")),
+            MockResponse::json(file_batch("in_progress", 0)),
+            MockResponse::json(file_batch("in_progress", 1)),
+            MockResponse::json(file_batch("completed", 2)),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let poll = PollOptions::default().with_interval(Duration::from_millis(1), Duration::from_millis(5));
+
+        let req = CreateVectorStoreRequest {
+            expires_after: Some(VectorStoreExpiration::days_after_last_active(7)),
+            ..CreateVectorStoreRequest::new("Support FAQ")
+        };
+        let store = sdk.create_vector_store(req).await?;
+        let mut req = CreateVectorStoreFileRequest::new(&store.id, "file-abc123");
+        req.attributes.insert("author".into(), "Ada".into());
+        req.chunking_strategy = Some(ChunkingStrategy::fixed(400, 100));
+        let file = sdk.create_vector_store_file(req).await?.wait(&sdk, poll).await?;
+        assert_eq!(file.status, VectorStoreFileStatus::Completed);
+        assert_eq!(file.attributes.unwrap()["year"], AttributeValue::Number(2024.0));
+
+        let req = CreateVectorStoreFileBatchRequest::new(&store.id, vec!["file-1".into(), "file-2".into()]);
+        let batch = sdk.create_vector_store_file_batch(req).await?.wait(&sdk, poll).await?;
+        assert_eq!(batch.file_counts.completed, 2);
+
+        let requests = server.requests();
+        assert_eq!(
+            server.calls(),
+            [
+                "POST /vector_stores",
+                "POST /vector_stores/vs_abc123/files",
+                "GET /vector_stores/vs_abc123/files/file-abc123",
+                "GET /vector_stores/vs_abc123/files/file-abc123",
+                "POST /vector_stores/vs_abc123/file_batches",
+                "GET /vector_stores/vs_abc123/file_batches/vsfb_abc123",
+                "GET /vector_stores/vs_abc123/file_batches/vsfb_abc123",
+            ]
+        );
+        assert_eq!(
+            requests[1].json(),
+            json!({
+                "file_id": "file-abc123",
+                "attributes": { "author": "Ada" },
+                "chunking_strategy": { "type": "static", "static": { "max_chunk_size_tokens": 400, "chunk_overlap_tokens": 100 } }
+            })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_with_filters() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(json!({
+            "object": "vector_store.search_results.page",
+            "search_query": ["refund policy"],
+            "data": [{
+                "file_id": "file-abc123",
+                "filename": "faq.md",
+                "score": 0.87,
+                "attributes": { "lang": "en", "published": true },
+                "content": [{ "type": "text", "text": "Refunds are issued" }, { "type": "text", "text": "within 14 days." }]
+            }],
+            "has_more": false,
+            "next_page": null
+        }))])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let req = SearchVectorStoreRequest {
+            filters: Some(AttributeFilter::and(vec![
+                AttributeFilter::eq("lang", "en"),
+                AttributeFilter::or(vec![AttributeFilter::gte("year", 2023), AttributeFilter::eq("pinned", true)]),
+            ])),
+            max_num_results: Some(5),
+            ..SearchVectorStoreRequest::new("vs_abc123", "How do refunds work?")
+        };
+        let res = sdk.search_vector_store(req).await?;
+        assert_eq!(res.data[0].text(), "Refunds are issued\nwithin 14 days.");
+        assert_eq!(res.data[0].attributes.as_ref().unwrap()["published"], AttributeValue::Bool(true));
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/vector_stores/vs_abc123/search");
+        assert_eq!(
+            req.json(),
+            json!({
+                "query": "How do refunds work?",
+                "filters": {
+                    "type": "and",
+                    "filters": [
+                        { "type": "eq", "key": "lang", "value": "en" },
+                        { "type": "or", "filters": [
+                            { "type": "gte", "key": "year", "value": 2023.0 },
+                            { "type": "eq", "key": "pinned", "value": true }
+                        ] }
+                    ]
+                },
+                "max_num_results": 5
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_chunking_strategy_validation() {
+        assert!(ChunkingStrategy::Auto.validate().is_ok());
+        assert!(ChunkingStrategy::fixed(800, 400).validate().is_ok());
+        assert!(ChunkingStrategy::fixed(50, 0).validate().is_err());
+        assert!(ChunkingStrategy::fixed(800, 401).validate().is_err());
+        let req = CreateVectorStoreFileBatchRequest::new("vs_abc123", vec![]);
+        assert!(matches!(req.validate(), Err(LlmError::Validation(_))));
+    }
+}
@@ -0,0 +1,596 @@
+use std::ops::Range;
+
+use bytes::Bytes;
+
+use crate::{CreateTranscriptionRequest, CreateTranscriptionResponse, FileSource, LlmError, Result};
+
+/// The largest file the transcription endpoints accept.
+pub const MAX_TRANSCRIPTION_FILE_SIZE: usize = 25 * 1024 * 1024;
+
+/// How `LLMSDK::create_transcription_chunked` splits a long WAV recording, or raw PCM audio in
+/// the format given to `pcm`. Chunks are cut at the quietest point near their size limit, so
+/// words are not split in half.
+///
+/// With a concurrency of n, the chunks are divided into n consecutive runs that are transcribed
+/// at the same time. Within a run, every chunk gets the tail of the previous chunk's transcript
+/// as its `prompt`; the first chunk of a run gets the prompt of the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranscriptionChunking {
+    /// Maximum size of a chunk in bytes, including the WAV header.
+    pub max_chunk_bytes: usize,
+    /// Number of runs transcribed at the same time.
+    pub concurrency: usize,
+    /// Maximum number of characters of the previous transcript passed as prompt.
+    pub prompt_tail_chars: usize,
+    /// The format of the file when it is raw PCM audio rather than a WAV file. Raw audio is
+    /// always sent as WAV chunks, as the API cannot tell its format.
+    pub pcm: Option<PcmFormat>,
+}
+
+/// The layout of raw PCM audio: little-endian integer samples, interleaved by channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcmFormat {
+    pub sample_rate: u32,
+    /// 8 (unsigned), 16, 24 or 32.
+    pub bits_per_sample: u16,
+    pub channels: u16,
+}
+
+impl Default for TranscriptionChunking {
+    fn default() -> Self {
+        TranscriptionChunking {
+            // leaves room for the multipart framing
+            max_chunk_bytes: MAX_TRANSCRIPTION_FILE_SIZE - 64 * 1024,
+            concurrency: 4,
+            prompt_tail_chars: 500,
+            pcm: None,
+        }
+    }
+}
+
+impl TranscriptionChunking {
+    /// Chunks raw PCM audio with the given format, e.g. `pcm(24000, 16, 1)` for the 16-bit mono
+    /// audio of the Realtime API.
+    pub fn pcm(sample_rate: u32, bits_per_sample: u16, channels: u16) -> Self {
+        TranscriptionChunking {
+            pcm: Some(PcmFormat {
+                sample_rate,
+                bits_per_sample,
+                channels,
+            }),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_chunk_bytes(mut self, max_chunk_bytes: usize) -> Self {
+        self.max_chunk_bytes = max_chunk_bytes;
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub fn with_prompt_tail_chars(mut self, prompt_tail_chars: usize) -> Self {
+        self.prompt_tail_chars = prompt_tail_chars;
+        self
+    }
+
+    /// Loads the file of the request and splits it into one request per chunk, each with the
+    /// offset of the chunk in seconds. A file within the limit is sent as is.
+    pub(crate) async fn split(&self, req: CreateTranscriptionRequest) -> Result<Vec<TranscriptionChunk>> {
+        if !req.response_format.unwrap_or_default().is_json() {
+            return Err(LlmError::Validation(
+                "chunked transcription needs the json or verbose_json response format, render subtitles with to_srt or to_vtt".into(),
+            ));
+        }
+        if self.concurrency == 0 {
+            return Err(LlmError::Validation("concurrency must be at least 1".into()));
+        }
+        let CreateTranscriptionRequest {
+            file,
+            model,
+            language,
+            prompt,
+            response_format,
+            temperature,
+            timestamp_granularities,
+        } = req;
+        let (filename, data) = file.load().await?;
+        let wav = match self.pcm {
+            Some(format) => Wav::from_pcm(format, data)?,
+            None if data.len() <= self.max_chunk_bytes => {
+                return Ok(vec![TranscriptionChunk {
+                    offset: 0.0,
+                    req: CreateTranscriptionRequest {
+                        file: FileSource::bytes(filename, data),
+                        model,
+                        language,
+                        prompt,
+                        response_format,
+                        temperature,
+                        timestamp_granularities,
+                    },
+                }]);
+            }
+            None => Wav::parse(data)?,
+        };
+        let stem = filename.rsplit_once('.').map_or(filename.as_str(), |(stem, _)| stem);
+        let parts: Vec<_> = wav
+            .split(self.max_chunk_bytes)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, frames)| {
+                let offset = frames.start as f64 / wav.sample_rate as f64;
+                (offset, FileSource::bytes(format!("{stem}-{i}.wav"), wav.encode(frames)))
+            })
+            .collect();
+        Ok(parts
+            .into_iter()
+            .map(|(offset, file)| TranscriptionChunk {
+                offset,
+                req: CreateTranscriptionRequest {
+                    file,
+                    model: model.clone(),
+                    language: language.clone(),
+                    prompt: prompt.clone(),
+                    response_format,
+                    temperature,
+                    timestamp_granularities: timestamp_granularities.clone(),
+                },
+            })
+            .collect())
+    }
+
+    /// Divides the chunks into at most `concurrency` consecutive runs of similar length.
+    pub(crate) fn runs(&self, chunks: Vec<TranscriptionChunk>) -> Vec<Vec<TranscriptionChunk>> {
+        let len = chunks.len().div_ceil(self.concurrency.max(1)).max(1);
+        let mut runs = vec![];
+        let mut chunks = chunks.into_iter().peekable();
+        while chunks.peek().is_some() {
+            runs.push(chunks.by_ref().take(len).collect());
+        }
+        runs
+    }
+
+    /// The end of a transcript, cut at a word boundary, to use as the prompt of the next chunk.
+    pub(crate) fn prompt_tail(&self, text: &str) -> Option<String> {
+        let text = text.trim();
+        let skip = text.chars().count().saturating_sub(self.prompt_tail_chars);
+        let tail: String = text.chars().skip(skip).collect();
+        let tail = match tail.split_once(char::is_whitespace) {
+            Some((_, rest)) if skip > 0 && !rest.trim().is_empty() => rest.trim(),
+            _ => tail.as_str(),
+        };
+        (!tail.is_empty()).then(|| tail.to_string())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TranscriptionChunk {
+    /// Start of the chunk in the original recording, in seconds.
+    pub offset: f64,
+    pub req: CreateTranscriptionRequest,
+}
+
+impl CreateTranscriptionResponse {
+    /// Joins the transcripts of consecutive chunks, shifting timestamps by the chunk offsets.
+    pub(crate) fn stitch(parts: Vec<(f64, CreateTranscriptionResponse)>) -> Self {
+        let mut stitched = CreateTranscriptionResponse::default();
+        let mut texts = vec![];
+        for (offset, part) in parts {
+            texts.push(part.text.trim().to_string());
+            stitched.language = stitched.language.or(part.language);
+            if let Some(duration) = part.duration {
+                stitched.duration = Some(offset + duration);
+            }
+            for mut segment in part.segments {
+                segment.id = stitched.segments.len();
+                segment.start += offset;
+                segment.end += offset;
+                stitched.segments.push(segment);
+            }
+            stitched.words.extend(part.words.into_iter().map(|mut word| {
+                word.start += offset;
+                word.end += offset;
+                word
+            }));
+        }
+        texts.retain(|text| !text.is_empty());
+        stitched.text = texts.join(" ");
+        stitched
+    }
+}
+
+const WAV_HEADER_LEN: usize = 44;
+
+/// Uncompressed audio read from a WAV file.
+#[derive(Debug)]
+pub(crate) struct Wav {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+    pub float: bool,
+    pub data: Bytes,
+}
+
+impl Wav {
+    pub fn from_pcm(format: PcmFormat, data: Bytes) -> Result<Self> {
+        if !matches!(format.bits_per_sample, 8 | 16 | 24 | 32) || format.channels == 0 || format.sample_rate == 0 {
+            return Err(LlmError::Validation(format!("cannot split audio: invalid PCM format {format:?}")));
+        }
+        Ok(Wav {
+            channels: format.channels,
+            sample_rate: format.sample_rate,
+            bits_per_sample: format.bits_per_sample,
+            float: false,
+            data,
+        })
+    }
+
+    pub fn parse(bytes: Bytes) -> Result<Self> {
+        let invalid = |msg: &str| LlmError::Validation(format!("cannot split audio: {msg}"));
+        if bytes.len() < 12 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(invalid("only WAV files can be chunked"));
+        }
+        let mut format = None;
+        let mut pos = 12;
+        while pos + 8 <= bytes.len() {
+            let id = &bytes[pos..pos + 4];
+            let size = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().unwrap()) as usize;
+            let body = pos + 8..(pos + 8).saturating_add(size).min(bytes.len());
+            match id {
+                b"fmt " if body.len() >= 16 => {
+                    let fmt = &bytes[body.clone()];
+                    let mut tag = u16::from_le_bytes([fmt[0], fmt[1]]);
+                    // WAVE_FORMAT_EXTENSIBLE keeps the actual format in the first two bytes of
+                    // its sub-format GUID
+                    if tag == 0xFFFE {
+                        if fmt.len() < 40 {
+                            return Err(invalid("truncated WAVE_FORMAT_EXTENSIBLE fmt chunk"));
+                        }
+                        tag = u16::from_le_bytes([fmt[24], fmt[25]]);
+                    }
+                    let channels = u16::from_le_bytes([fmt[2], fmt[3]]);
+                    let sample_rate = u32::from_le_bytes(fmt[4..8].try_into().unwrap());
+                    let bits_per_sample = u16::from_le_bytes([fmt[14], fmt[15]]);
+                    format = Some((tag, channels, sample_rate, bits_per_sample));
+                }
+                b"data" => {
+                    let (tag, channels, sample_rate, bits_per_sample) =
+                        format.ok_or_else(|| invalid("data before fmt chunk"))?;
+                    let float = match (tag, bits_per_sample) {
+                        (1, 8 | 16 | 24 | 32) => false,
+                        (3, 32) => true,
+                        _ => return Err(invalid("only PCM and 32-bit float samples are supported")),
+                    };
+                    if channels == 0 || sample_rate == 0 {
+                        return Err(invalid("invalid fmt chunk"));
+                    }
+                    return Ok(Wav {
+                        channels,
+                        sample_rate,
+                        bits_per_sample,
+                        float,
+                        data: bytes.slice(body),
+                    });
+                }
+                _ => {}
+            }
+            // chunks are padded to an even size
+            pos = body.end + (size & 1);
+        }
+        Err(invalid("no data chunk"))
+    }
+
+    fn block_align(&self) -> usize {
+        self.channels as usize * (self.bits_per_sample as usize / 8)
+    }
+
+    pub fn frames(&self) -> usize {
+        self.data.len() / self.block_align()
+    }
+
+    /// Splits the frames into ranges whose encoded size stays within `max_bytes`, cutting each
+    /// one at the quietest 10ms window of its last quarter (at most 30 seconds).
+    pub fn split(&self, max_bytes: usize) -> Result<Vec<Range<usize>>> {
+        let max_frames = max_bytes.saturating_sub(WAV_HEADER_LEN) / self.block_align();
+        let window = (self.sample_rate as usize / 100).max(1);
+        if max_frames < window * 4 {
+            return Err(LlmError::Validation(format!(
+                "max_chunk_bytes of {max_bytes} is too small for this audio"
+            )));
+        }
+        let search = (max_frames / 4).min(self.sample_rate as usize * 30);
+        let frames = self.frames();
+        let mut ranges = vec![];
+        let mut start = 0;
+        while frames - start > max_frames {
+            let limit = start + max_frames;
+            let mut cut = limit;
+            let mut quietest = f32::MAX;
+            let mut end = limit;
+            while end >= window && end - window >= limit - search {
+                let loudness = self.loudness(end - window..end);
+                // prefer the latest window among equally quiet ones to keep chunks long
+                if loudness < quietest {
+                    quietest = loudness;
+                    cut = end - window / 2;
+                }
+                end -= window;
+            }
+            ranges.push(start..cut);
+            start = cut;
+        }
+        ranges.push(start..frames);
+        Ok(ranges)
+    }
+
+    /// Mean absolute amplitude of the frames, between 0 and 1.
+    fn loudness(&self, frames: Range<usize>) -> f32 {
+        let sample_len = self.bits_per_sample as usize / 8;
+        let bytes = &self.data[frames.start * self.block_align()..frames.end * self.block_align()];
+        let samples = bytes.chunks_exact(sample_len);
+        let count = samples.len().max(1) as f32;
+        samples.map(|s| self.amplitude(s).abs()).sum::<f32>() / count
+    }
+
+    fn amplitude(&self, s: &[u8]) -> f32 {
+        match (self.float, s.len()) {
+            (true, _) => f32::from_le_bytes(s.try_into().unwrap()),
+            (false, 1) => (s[0] as f32 - 128.0) / 128.0,
+            (false, 2) => i16::from_le_bytes([s[0], s[1]]) as f32 / 32768.0,
+            (false, 3) => (i32::from_le_bytes([0, s[0], s[1], s[2]]) >> 8) as f32 / 8_388_608.0,
+            (false, _) => i32::from_le_bytes(s.try_into().unwrap()) as f32 / 2_147_483_648.0,
+        }
+    }
+
+    /// Encodes the frames as a standalone WAV file.
+    pub fn encode(&self, frames: Range<usize>) -> Vec<u8> {
+        let data = &self.data[frames.start * self.block_align()..frames.end * self.block_align()];
+        let mut out = Vec::with_capacity(WAV_HEADER_LEN + data.len());
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        out.extend_from_slice(&(if self.float { 3u16 } else { 1 }).to_le_bytes());
+        out.extend_from_slice(&self.channels.to_le_bytes());
+        out.extend_from_slice(&self.sample_rate.to_le_bytes());
+        out.extend_from_slice(&(self.sample_rate * self.block_align() as u32).to_le_bytes());
+        out.extend_from_slice(&(self.block_align() as u16).to_le_bytes());
+        out.extend_from_slice(&self.bits_per_sample.to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        TimestampGranularity, TranscriptionResponseFormat, LLMSDK,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    const RATE: u32 = 8000;
+
+    /// 16-bit mono audio: a tone for every `(seconds, loud)` pair, or silence.
+    fn wav(parts: &[(f64, bool)]) -> Vec<u8> {
+        let mut data = vec![];
+        for &(seconds, loud) in parts {
+            for i in 0..(seconds * RATE as f64) as usize {
+                let sample = if loud { if i % 16 < 8 { 8000i16 } else { -8000 } } else { 0 };
+                data.extend_from_slice(&sample.to_le_bytes());
+            }
+        }
+        let wav = Wav {
+            channels: 1,
+            sample_rate: RATE,
+            bits_per_sample: 16,
+            float: false,
+            data: data.into(),
+        };
+        wav.encode(0..wav.frames())
+    }
+
+    /// A WAVE_FORMAT_EXTENSIBLE header with the given sub-format, followed by `data`.
+    fn extensible_wav(sub_format: u16, bits_per_sample: u16, data: &[u8]) -> Vec<u8> {
+        let block_align = bits_per_sample / 8;
+        let mut out = b"RIFF\0\0\0\0WAVEfmt ".to_vec();
+        out.extend_from_slice(&40u32.to_le_bytes());
+        out.extend_from_slice(&0xFFFEu16.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&RATE.to_le_bytes());
+        out.extend_from_slice(&(RATE * block_align as u32).to_le_bytes());
+        out.extend_from_slice(&block_align.to_le_bytes());
+        out.extend_from_slice(&bits_per_sample.to_le_bytes());
+        out.extend_from_slice(&22u16.to_le_bytes());
+        out.extend_from_slice(&bits_per_sample.to_le_bytes());
+        out.extend_from_slice(&4u32.to_le_bytes());
+        out.extend_from_slice(&sub_format.to_le_bytes());
+        out.extend_from_slice(b"\x00\x00\x00\x00\x10\x00\x80\x00\x00\xAA\x00\x38\x9B\x71");
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+
+    fn seconds(range: &Range<usize>) -> (f64, f64) {
+        (range.start as f64 / RATE as f64, range.end as f64 / RATE as f64)
+    }
+
+    #[test]
+    fn test_wav_split_at_silence() -> Result<()> {
+        let data = wav(&[(1.7, true), (0.2, false), (1.6, true), (0.3, false), (1.0, true)]);
+        let wav = Wav::parse(data.into())?;
+        assert_eq!(wav.frames(), 4 * RATE as usize + 6400);
+        // two seconds per chunk
+        let ranges = wav.split(WAV_HEADER_LEN + 2 * 2 * RATE as usize)?;
+        assert_eq!(ranges.len(), 3);
+        let (_, end) = seconds(&ranges[0]);
+        assert!((1.7..=1.9).contains(&end), "first cut at {end}");
+        let (start, end) = seconds(&ranges[1]);
+        assert!((3.5..=3.8).contains(&end), "second cut at {end}");
+        assert!(end - start <= 2.0);
+        assert_eq!(ranges[2].end, wav.frames());
+
+        let chunk = Wav::parse(wav.encode(ranges[1].clone()).into())?;
+        assert_eq!(chunk.frames(), ranges[1].len());
+        assert!(Wav::parse(Bytes::from_static(b"ID3 not a wav file")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_wav_extensible_sub_formats() -> Result<()> {
+        let samples: Vec<u8> = [0.5f32, -0.25].iter().flat_map(|s| s.to_le_bytes()).collect();
+        let wav = Wav::parse(extensible_wav(3, 32, &samples).into())?;
+        assert!(wav.float);
+        assert_eq!(wav.loudness(0..2), 0.375);
+        let chunk = Wav::parse(wav.encode(0..2).into())?;
+        assert!(chunk.float);
+
+        let wav = Wav::parse(extensible_wav(1, 16, &[0, 0x40, 0, 0xC0]).into())?;
+        assert!(!wav.float);
+        assert_eq!(wav.loudness(0..2), 0.5);
+        // e.g. KSDATAFORMAT_SUBTYPE_MULAW
+        assert!(Wav::parse(extensible_wav(7, 32, &samples).into()).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_split_raw_pcm() -> Result<()> {
+        let data = Wav::parse(wav(&[(1.7, true), (0.2, false), (1.6, true)]).into())?.data;
+        let req = CreateTranscriptionRequest {
+            response_format: Some(TranscriptionResponseFormat::VerboseJson),
+            ..CreateTranscriptionRequest::new(FileSource::bytes("talk.pcm", data))
+        };
+        let chunking = TranscriptionChunking::pcm(RATE, 16, 1).with_max_chunk_bytes(WAV_HEADER_LEN + 2 * 2 * RATE as usize);
+        let chunks = chunking.split(req).await?;
+        assert_eq!(chunks.len(), 2);
+        let (filename, data) = chunks.into_iter().nth(1).unwrap().req.file.load().await?;
+        assert_eq!(filename, "talk-1.wav");
+        let chunk = Wav::parse(data)?;
+        assert_eq!((chunk.sample_rate, chunk.bits_per_sample, chunk.channels), (RATE, 16, 1));
+
+        // raw audio within the size limit is still sent as WAV
+        let req = CreateTranscriptionRequest::new(FileSource::bytes("short.pcm", vec![0u8; 3200]));
+        let chunks = TranscriptionChunking::pcm(RATE, 16, 1).split(req).await?;
+        let (filename, data) = chunks.into_iter().next().unwrap().req.file.load().await?;
+        assert_eq!(filename, "short-0.wav");
+        assert_eq!(Wav::parse(data)?.frames(), 1600);
+
+        let req = CreateTranscriptionRequest::new(FileSource::bytes("bad.pcm", vec![0u8; 4]));
+        let res = TranscriptionChunking::pcm(RATE, 12, 1).split(req).await;
+        assert!(matches!(res, Err(LlmError::Validation(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_prompt_tail_and_runs() {
+        let chunking = TranscriptionChunking::default().with_prompt_tail_chars(12);
+        assert_eq!(chunking.prompt_tail("the quick brown fox jumps").as_deref(), Some("fox jumps"));
+        assert_eq!(chunking.prompt_tail("short").as_deref(), Some("short"));
+        assert_eq!(chunking.prompt_tail("  "), None);
+
+        let runs = |chunks: usize, concurrency: usize| {
+            let chunks = (0..chunks)
+                .map(|i| TranscriptionChunk {
+                    offset: i as f64,
+                    req: CreateTranscriptionRequest::new("a.wav"),
+                })
+                .collect();
+            let runs = TranscriptionChunking::default().with_concurrency(concurrency).runs(chunks);
+            runs.iter().map(Vec::len).collect::<Vec<_>>()
+        };
+        assert_eq!(runs(5, 2), [3, 2]);
+        assert_eq!(runs(3, 4), [1, 1, 1]);
+        assert_eq!(runs(4, 1), [4]);
+    }
+
+    fn segment_response(text: &str) -> MockResponse {
+        MockResponse::json(json!({
+            "language": "english",
+            "duration": 1.5,
+            "text": text,
+            "segments": [{ "id": 0, "start": 0.25, "end": 1.0, "text": text }],
+            "words": [{ "word": text, "start": 0.25, "end": 1.0 }]
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_chunked_transcription_passes_prompt_tail() -> Result<()> {
+        let server = MockServer::start(vec![
+            segment_response("one two three"),
+            segment_response("four five"),
+            segment_response("six"),
+        ])
+        .await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let data = wav(&[(1.7, true), (0.2, false), (1.6, true), (0.3, false), (1.0, true)]);
+        let req = CreateTranscriptionRequest {
+            prompt: Some("A talk.".into()),
+            response_format: Some(TranscriptionResponseFormat::VerboseJson),
+            timestamp_granularities: vec![TimestampGranularity::Segment, TimestampGranularity::Word],
+            ..CreateTranscriptionRequest::new(FileSource::bytes("talk.wav", data))
+        };
+        let chunking = TranscriptionChunking::default()
+            .with_max_chunk_bytes(WAV_HEADER_LEN + 2 * 2 * RATE as usize)
+            .with_concurrency(1)
+            .with_prompt_tail_chars(10);
+        let res = sdk.create_transcription_chunked(req, chunking).await?;
+        assert_eq!(res.text, "one two three four five six");
+        assert_eq!(res.segments.iter().map(|s| s.id).collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(res.segments[0].start, 0.25);
+        let second = res.segments[1].start - 0.25;
+        assert!((1.7..=1.9).contains(&second), "second chunk starts at {second}");
+        assert_eq!(res.words[2].start, res.segments[2].start);
+        assert_eq!(res.language.as_deref(), Some("english"));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        let prompts: Vec<_> = requests
+            .iter()
+            .map(|req| {
+                let body = String::from_utf8_lossy(&req.body).into_owned();
+                let (_, rest) = body.split_once("name=\"prompt\"\r\n\r\n").unwrap();
+                rest.split_once("\r\n").unwrap().0.to_string()
+            })
+            .collect();
+        assert_eq!(prompts, ["A talk.", "two three", "four five"]);
+        assert!(String::from_utf8_lossy(&requests[1].body).contains("filename=\"talk-1.wav\"\r\nContent-Type: audio/wav"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunked_transcription_concurrent() -> Result<()> {
+        let server = MockServer::start(vec![segment_response("hello")]).await;
+        let sdk = LLMSDK::builder().base_url(&server.url).build()?;
+        let data = wav(&[(1.0, true), (0.5, false), (1.0, true), (0.5, false), (1.0, true), (0.5, false), (1.0, true)]);
+        let req = CreateTranscriptionRequest {
+            response_format: Some(TranscriptionResponseFormat::VerboseJson),
+            ..CreateTranscriptionRequest::new(FileSource::bytes("talk.wav", data))
+        };
+        let chunking = TranscriptionChunking::default()
+            .with_max_chunk_bytes(WAV_HEADER_LEN + 2 * 2 * RATE as usize)
+            .with_concurrency(4);
+        let res = sdk.create_transcription_chunked(req, chunking).await?;
+        assert_eq!(server.requests().len(), 3);
+        assert_eq!(res.text, "hello hello hello");
+        let starts: Vec<_> = res.segments.iter().map(|s| s.start).collect();
+        assert!(starts.windows(2).all(|w| w[1] - w[0] > 1.0), "segments start at {starts:?}");
+
+        let req = CreateTranscriptionRequest {
+            response_format: Some(TranscriptionResponseFormat::Srt),
+            ..CreateTranscriptionRequest::new(FileSource::bytes("talk.wav", wav(&[(1.0, true)])))
+        };
+        let res = sdk.create_transcription_chunked(req, TranscriptionChunking::default()).await;
+        assert!(matches!(res, Err(LlmError::Validation(_))));
+        Ok(())
+    }
+}
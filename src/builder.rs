@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Client, Proxy,
+};
+
+use crate::{LlmError, RequestContext, Result, RetryPolicy, BASE_URL, LLMSDK, TIMEOUT};
+
+/// Configures an `LLMSDK`. Created with `LLMSDK::builder()`.
+#[derive(Debug, Default)]
+pub struct LLMSDKBuilder {
+    token: Option<String>,
+    base_url: Option<String>,
+    api_version: Option<String>,
+    organization: Option<String>,
+    project: Option<String>,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    client: Option<Client>,
+    retry_policy: Option<RetryPolicy>,
+}
+
+impl LLMSDKBuilder {
+    /// Fills the token and base URL from `OPENAI_API_KEY` and `OPENAI_BASE_URL`, and the
+    /// organization and project from `OPENAI_ORG_ID` and `OPENAI_PROJECT_ID`. Values that were
+    /// already set on the builder take precedence.
+    pub fn from_env(mut self) -> Self {
+        let var = |name| std::env::var(name).ok().filter(|v: &String| !v.is_empty());
+        self.token = self.token.or_else(|| var("OPENAI_API_KEY"));
+        self.base_url = self.base_url.or_else(|| var("OPENAI_BASE_URL"));
+        self.organization = self.organization.or_else(|| var("OPENAI_ORG_ID"));
+        self.project = self.project.or_else(|| var("OPENAI_PROJECT_ID"));
+        self
+    }
+
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// The API root every endpoint path is appended to. Defaults to `https://api.openai.com/v1`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Sent as the `api-version` query parameter of every request, as Azure OpenAI requires.
+    pub fn api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.api_version = Some(api_version.into());
+        self
+    }
+
+    /// Sent as the `OpenAI-Organization` header.
+    pub fn organization(mut self, organization: impl Into<String>) -> Self {
+        self.organization = Some(organization.into());
+        self
+    }
+
+    /// Sent as the `OpenAI-Project` header.
+    pub fn project(mut self, project: impl Into<String>) -> Self {
+        self.project = Some(project.into());
+        self
+    }
+
+    /// A header sent with every request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Total timeout of a non-streaming request. Defaults to 30 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Uses an existing client instead of building one. Proxy and connect timeout must then be
+    /// configured on that client.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    pub fn build(self) -> Result<LLMSDK> {
+        let mut headers = HeaderMap::new();
+        let named = [
+            ("openai-organization", self.organization),
+            ("openai-project", self.project),
+        ];
+        let named = named
+            .into_iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value?)));
+        for (name, value) in named.chain(self.headers) {
+            let name = HeaderName::try_from(name.as_str())
+                .map_err(|_| LlmError::Validation(format!("invalid header name: {name}")))?;
+            let value = HeaderValue::try_from(value)
+                .map_err(|_| LlmError::Validation(format!("invalid value for header {name}")))?;
+            headers.insert(name, value);
+        }
+
+        let custom_transport = self.client.is_some() || self.proxy.is_some();
+        let client = match self.client {
+            Some(_) if self.proxy.is_some() || self.connect_timeout.is_some() => {
+                return Err(LlmError::Validation(
+                    "proxy and connect timeout cannot be applied to a user-supplied client".into(),
+                ));
+            }
+            Some(client) => client,
+            None => {
+                let mut builder = Client::builder();
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                builder.build()?
+            }
+        };
+
+        let base_url = self.base_url.unwrap_or_else(|| BASE_URL.to_string());
+        let mut ctx = RequestContext::new(base_url, client).with_headers(headers);
+        if let Some(api_version) = self.api_version {
+            ctx = ctx.with_api_version(api_version);
+        }
+
+        Ok(LLMSDK {
+            token: self.token.unwrap_or_default(),
+            ctx,
+            timeout: self.timeout.unwrap_or(Duration::from_secs(TIMEOUT)),
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            custom_transport,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mock::{MockResponse, MockServer},
+        ChatCompletionMessage, ChatCompletionModel, ChatCompletionRequest,
+    };
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_builder_routes_through_gateway() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1,
+            "model": "gpt-4o-mini",
+            "choices": [{ "index": 0, "message": { "role": "assistant", "content": "hi" }, "finish_reason": "stop" }]
+        }))])
+        .await;
+        let sdk = LLMSDK::builder()
+            .token("sk-test")
+            .base_url(format!("{}/gateway/v1/", server.url))
+            .organization("org-1")
+            .project("proj-1")
+            .header("x-team", "search")
+            .timeout(Duration::from_secs(5))
+            .connect_timeout(Duration::from_secs(1))
+            .build()?;
+        let req = ChatCompletionRequest::new(ChatCompletionModel::Gpt4oMini, [ChatCompletionMessage::user("hi")]);
+        let res = sdk.chat_completion(req).await?;
+        assert_eq!(res.choices[0].message.content.as_deref(), Some("hi"));
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/gateway/v1/chat/completions");
+        assert_eq!(req.header("authorization"), Some("Bearer sk-test"));
+        assert_eq!(req.header("openai-organization"), Some("org-1"));
+        assert_eq!(req.header("openai-project"), Some("proj-1"));
+        assert_eq!(req.header("x-team"), Some("search"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_builder_azure_style_deployment() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::json(json!({ "created": 1, "data": [] }))]).await;
+        let sdk = LLMSDK::builder()
+            .base_url(format!("{}/openai/deployments/dalle", server.url))
+            .api_version("2024-02-01")
+            .header("api-key", "azure-key")
+            .build()?;
+        sdk.create_image(crate::CreateImageRequest::new("a cat")).await?;
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/openai/deployments/dalle/images/generations?api-version=2024-02-01");
+        assert_eq!(req.header("api-key"), Some("azure-key"));
+        assert_eq!(req.header("authorization"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_config() {
+        let res = LLMSDK::builder().header("bad header", "v").build();
+        assert!(matches!(res, Err(LlmError::Validation(_))));
+        let res = LLMSDK::builder()
+            .client(Client::new())
+            .connect_timeout(Duration::from_secs(1))
+            .build();
+        assert!(matches!(res, Err(LlmError::Validation(_))));
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_util::{stream, stream::BoxStream, Stream, StreamExt};
+use reqwest::Response;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::Result;
+
+/// A response body read piece by piece as it arrives, e.g. generated audio or file content.
+pub struct ByteStream {
+    content_type: Option<String>,
+    inner: BoxStream<'static, Result<Bytes>>,
+}
+
+impl ByteStream {
+    pub(crate) fn new(res: Response) -> Self {
+        let content_type = res
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let inner = stream::unfold(Some(res), |res| async move {
+            let mut res = res?;
+            match res.chunk().await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(res))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e.into()), None)),
+            }
+        });
+        ByteStream {
+            content_type,
+            inner: inner.boxed(),
+        }
+    }
+
+    /// The `Content-Type` the server sent, e.g. `audio/mpeg`.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Copies the rest of the body into the writer and flushes it. Returns the number of bytes written.
+    pub async fn write_to<W: AsyncWrite + Unpin + ?Sized>(mut self, writer: &mut W) -> Result<u64> {
+        let mut written = 0;
+        while let Some(chunk) = self.inner.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    /// Reads the rest of the body into memory.
+    pub async fn bytes(mut self) -> Result<Bytes> {
+        let mut data = vec![];
+        while let Some(chunk) = self.inner.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        Ok(data.into())
+    }
+}
+
+impl Stream for ByteStream {
+    type Item = Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl fmt::Debug for ByteStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ByteStream")
+            .field("content_type", &self.content_type)
+            .finish_non_exhaustive()
+    }
+}
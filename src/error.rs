@@ -0,0 +1,182 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+pub type Result<T, E = LlmError> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum LlmError {
+    /// The request could not be sent or the response could not be read.
+    Transport(reqwest::Error),
+    /// The request did not complete within the configured timeout.
+    Timeout(reqwest::Error),
+    /// The API answered with an error. `error` is the parsed error object when the body had one.
+    Api {
+        status: StatusCode,
+        error: Option<ApiError>,
+        body: String,
+    },
+    /// The response body did not match the expected type. The raw body is kept for inspection.
+    Deserialize {
+        source: serde_json::Error,
+        body: String,
+    },
+    /// A file to upload or write could not be accessed.
+    Io(std::io::Error),
+    /// The request was rejected locally before being sent.
+    Validation(String),
+    /// A streamed response ended before it was complete.
+    IncompleteStream(String),
+    /// A WebSocket connection failed its handshake, broke the protocol or was closed abnormally.
+    WebSocket(String),
+}
+
+/// The `error` object OpenAI returns with a failed request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ApiError {
+    /// A human-readable error message.
+    pub message: String,
+    /// The error type, e.g. invalid_request_error or insufficient_quota.
+    #[serde(default)]
+    pub r#type: Option<String>,
+    /// The request parameter that caused the error, if any.
+    #[serde(default)]
+    pub param: Option<String>,
+    /// A machine-readable error code, e.g. rate_limit_exceeded.
+    #[serde(default)]
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ApiErrorResponse {
+    pub error: ApiError,
+}
+
+impl LlmError {
+    /// Builds the error for a response with a non-success status from its body.
+    pub(crate) fn from_response(status: StatusCode, body: String) -> Self {
+        let error = serde_json::from_str::<ApiErrorResponse>(&body)
+            .ok()
+            .map(|res| res.error);
+        LlmError::Api { status, error, body }
+    }
+
+    /// The HTTP status of an API error.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            LlmError::Api { status, .. } => Some(*status),
+            LlmError::Transport(e) | LlmError::Timeout(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    /// The parsed error object of an API error.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            LlmError::Api { error, .. } => error.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self.status(), Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN))
+    }
+
+    pub fn is_bad_request(&self) -> bool {
+        self.status() == Some(StatusCode::BAD_REQUEST)
+    }
+
+    pub fn is_server_error(&self) -> bool {
+        self.status().is_some_and(|status| status.is_server_error())
+    }
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LlmError::Transport(e) => write!(f, "transport error: {e}"),
+            LlmError::Timeout(e) => write!(f, "request timed out: {e}"),
+            LlmError::Api { status, error: Some(error), .. } => {
+                write!(f, "api error ({status}): {}", error.message)
+            }
+            LlmError::Api { status, body, .. } => write!(f, "api error ({status}): {body}"),
+            LlmError::Deserialize { source, .. } => {
+                write!(f, "failed to deserialize response: {source}")
+            }
+            LlmError::Io(e) => write!(f, "io error: {e}"),
+            LlmError::Validation(msg) => write!(f, "invalid request: {msg}"),
+            LlmError::IncompleteStream(msg) => write!(f, "incomplete stream: {msg}"),
+            LlmError::WebSocket(msg) => write!(f, "websocket error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for LlmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LlmError::Transport(e) | LlmError::Timeout(e) => Some(e),
+            LlmError::Deserialize { source, .. } => Some(source),
+            LlmError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for LlmError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            LlmError::Timeout(e)
+        } else {
+            LlmError::Transport(e)
+        }
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for LlmError {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        use tokio_tungstenite::tungstenite::Error;
+        match e {
+            Error::Http(res) => {
+                let body = res.body().as_deref().map(String::from_utf8_lossy).unwrap_or_default();
+                LlmError::from_response(res.status(), body.into_owned())
+            }
+            Error::Io(e) => LlmError::Io(e),
+            e => LlmError::WebSocket(e.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for LlmError {
+    fn from(e: std::io::Error) -> Self {
+        LlmError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_from_response() {
+        let body = r#"{"error": {"message": "Rate limit reached", "type": "requests", "param": null, "code": "rate_limit_exceeded"}}"#;
+        let err = LlmError::from_response(StatusCode::TOO_MANY_REQUESTS, body.into());
+        assert!(err.is_rate_limited());
+        assert!(!err.is_auth_error());
+        let api_error = err.api_error().unwrap();
+        assert_eq!(api_error.code.as_deref(), Some("rate_limit_exceeded"));
+        assert_eq!(err.to_string(), "api error (429 Too Many Requests): Rate limit reached");
+    }
+
+    #[test]
+    fn test_api_error_unparsable_body() {
+        let err = LlmError::from_response(StatusCode::BAD_GATEWAY, "<html>bad gateway</html>".into());
+        assert!(err.is_server_error());
+        assert!(err.api_error().is_none());
+        assert!(matches!(err, LlmError::Api { ref body, .. } if body.contains("bad gateway")));
+    }
+}
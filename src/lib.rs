@@ -1,49 +1,849 @@
-use std::time::Duration;
-use anyhow::{Result, Ok};
-use reqwest::{Client, RequestBuilder, Response};
+use std::time::{Duration, Instant};
+use reqwest::{header::{HeaderMap, HeaderValue, AUTHORIZATION}, Client, Method, RequestBuilder, Url};
+use serde::de::DeserializeOwned;
 
 mod api;
+mod audio;
+mod builder;
+mod byte_stream;
+mod error;
+#[cfg(test)]
+mod mock;
+mod multipart;
+mod poll;
+mod retry;
+mod sse;
+mod tool;
+mod websocket;
 
 pub use api::*;
+pub use audio::{PcmFormat, TranscriptionChunking, MAX_TRANSCRIPTION_FILE_SIZE};
+pub use builder::LLMSDKBuilder;
+pub use byte_stream::ByteStream;
+pub use error::{ApiError, LlmError, Result};
+pub use multipart::{FileSource, MultipartRequest};
+pub use poll::PollOptions;
+pub use retry::RetryPolicy;
+pub use tool::{ObjectSchema, ToSchema, ToolFunction};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+    pub use serde_json;
+}
 
 const TIMEOUT: u64 = 30;
+const BASE_URL: &str = "https://api.openai.com/v1";
+const OPENAI_BETA: &str = "openai-beta";
 
 pub struct LLMSDK {
     pub(crate) token: String,
-    pub(crate) client: Client,
+    pub(crate) ctx: RequestContext,
+    pub(crate) timeout: Duration,
+    pub(crate) retry_policy: RetryPolicy,
+    /// Set when the builder was given a proxy or a client, which the realtime WebSocket cannot use.
+    pub(crate) custom_transport: bool,
 }
 
 pub trait IntoRequest {
-    fn into_request(self, client: Client) -> RequestBuilder;
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder;
+
+    /// The `OpenAI-Beta` header a beta endpoint requires, e.g. `assistants=v2`. The SDK sends it
+    /// unless the context headers already set one.
+    fn beta(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Where and how requests are sent: the API root, an optional `api-version` query parameter
+/// (as Azure OpenAI expects) and the headers added to every request. Endpoints only know
+/// their path relative to the base URL.
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    base_url: String,
+    api_version: Option<String>,
+    headers: HeaderMap,
+    client: Client,
+}
+
+impl RequestContext {
+    pub fn new(base_url: impl Into<String>, client: Client) -> Self {
+        let base_url = base_url.into();
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_version: None,
+            headers: HeaderMap::new(),
+            client,
+        }
+    }
+    
+    pub fn with_api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.api_version = Some(api_version.into());
+        self
+    }
+    
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+    
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+    
+    pub fn api_version(&self) -> Option<&str> {
+        self.api_version.as_deref()
+    }
+    
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+    
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+    
+    /// The absolute url of an endpoint path such as `chat/completions`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+    
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let req = self
+            .client
+            .request(method, self.url(path))
+            .headers(self.headers.clone());
+        match &self.api_version {
+            Some(version) => req.query(&[("api-version", version)]),
+            None => req,
+        }
+    }
+    
+    pub fn get(&self, path: &str) -> RequestBuilder {
+        self.request(Method::GET, path)
+    }
+    
+    pub fn post(&self, path: &str) -> RequestBuilder {
+        self.request(Method::POST, path)
+    }
+    
+    pub fn delete(&self, path: &str) -> RequestBuilder {
+        self.request(Method::DELETE, path)
+    }
 }
 
 impl LLMSDK {
     pub fn new(token: String) -> Self {
         Self {
             token,
-            client: Client::new(),
+            ctx: RequestContext::new(BASE_URL, Client::new()),
+            timeout: Duration::from_secs(TIMEOUT),
+            retry_policy: RetryPolicy::default(),
+            custom_transport: false,
+        }
+    }
+    
+    pub fn builder() -> LLMSDKBuilder {
+        LLMSDKBuilder::default()
+    }
+    
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+    
+    pub async fn chat_completion(&self, req: impl IntoRequest) -> Result<ChatCompletionResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ChatCompletionResponse>(res).await
+    }
+    
+    pub async fn chat_completion_stream(&self, mut req: ChatCompletionRequest) -> Result<ChatCompletionStream> {
+        req.stream = Some(true);
+        let req = self.prepare_stream_request(req);
+        let res = self.send(req).await?;
+        Ok(Box::pin(sse::sse_stream(res)))
+    }
+    
+    /// Creates embeddings, splitting the input into as many requests as the API limits require.
+    /// Batches are sent one after another and merged into one response in input order.
+    pub async fn create_embedding(&self, req: CreateEmbeddingRequest) -> Result<CreateEmbeddingResponse> {
+        self.create_embedding_batched(req, MAX_EMBEDDING_INPUTS, MAX_EMBEDDING_TOKENS).await
+    }
+    
+    /// Like `create_embedding` with custom per-request input and token limits.
+    pub async fn create_embedding_batched(
+        &self,
+        req: CreateEmbeddingRequest,
+        max_inputs: usize,
+        max_tokens: usize,
+    ) -> Result<CreateEmbeddingResponse> {
+        let mut merged: Option<CreateEmbeddingResponse> = None;
+        for req in req.split(max_inputs, max_tokens) {
+            let req = self.prepare_request(req);
+            let res = self.send(req).await?;
+            let res = json::<CreateEmbeddingResponse>(res).await?;
+            match merged.as_mut() {
+                Some(merged) => merged.merge(res),
+                None => merged = Some(res),
+            }
         }
+        Ok(merged.expect("split returns at least one request"))
+    }
+    
+    pub async fn create_image(&self, req: CreateImageRequest) -> Result<CreateImageResponse> {
+        req.validate()?;
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<CreateImageResponse>(res).await
+    }
+    
+    pub async fn create_image_edit(&self, req: CreateImageEditRequest) -> Result<CreateImageResponse> {
+        let req = self.prepare_request(req.into_multipart().await?);
+        let res = self.send(req).await?;
+        json::<CreateImageResponse>(res).await
     }
     
-    // pub async fn chat_completion(&self, req: impl IntoRequest) -> Result<ChatCompletionResponse> {
-    //     let req = self.prepare_request(req);
-    //     let res = req.send().await?;
-    //     Ok(res.json::<ChatCompletionResponse>().await?)
-    // }
+    pub async fn create_image_variation(&self, req: CreateImageVariationRequest) -> Result<CreateImageResponse> {
+        let req = self.prepare_request(req.into_multipart().await?);
+        let res = self.send(req).await?;
+        json::<CreateImageResponse>(res).await
+    }
     
-    pub async fn create_image(&self, req: impl IntoRequest) -> Result<CreateImageResponse> {
+    pub async fn create_moderation(&self, req: CreateModerationRequest) -> Result<CreateModerationResponse> {
+        req.validate()?;
         let req = self.prepare_request(req);
-        let res = req.send().await?;
-        Ok(res.json::<CreateImageResponse>().await?)
+        let res = self.send(req).await?;
+        json::<CreateModerationResponse>(res).await
+    }
+    
+    /// Generates speech for the input. The audio is returned as it is generated; no total timeout
+    /// is applied, like for other streamed responses.
+    pub async fn create_speech(&self, req: CreateSpeechRequest) -> Result<ByteStream> {
+        req.validate()?;
+        let req = self.prepare_stream_request(req);
+        let res = self.send(req).await?;
+        Ok(ByteStream::new(res))
+    }
+    
+    /// Transcribes audio. For the text, srt and vtt response formats the body is returned as the
+    /// response's `text`.
+    pub async fn create_transcription(&self, req: CreateTranscriptionRequest) -> Result<CreateTranscriptionResponse> {
+        let format = req.response_format.unwrap_or_default();
+        let req = self.prepare_request(req.into_multipart().await?);
+        let res = self.send(req).await?;
+        if format.is_json() {
+            json::<CreateTranscriptionResponse>(res).await
+        } else {
+            Ok(CreateTranscriptionResponse::from_text(res.text().await?))
+        }
+    }
+    
+    /// Translates audio into English. For the text, srt and vtt response formats the body is
+    /// returned as the response's `text`.
+    pub async fn create_translation(&self, req: CreateTranslationRequest) -> Result<CreateTranslationResponse> {
+        let format = req.response_format.unwrap_or_default();
+        let req = self.prepare_request(req.into_multipart().await?);
+        let res = self.send(req).await?;
+        if format.is_json() {
+            json::<CreateTranslationResponse>(res).await
+        } else {
+            Ok(CreateTranslationResponse::from_text(res.text().await?))
+        }
+    }
+    
+    /// Transcribes a recording of any length. WAV files over the chunk size, and raw PCM audio
+    /// (see `TranscriptionChunking::pcm`), are split at silence, transcribed as described on
+    /// `TranscriptionChunking`, and stitched back together with the timestamps of every chunk
+    /// shifted by its offset in the recording.
+    pub async fn create_transcription_chunked(
+        &self,
+        req: CreateTranscriptionRequest,
+        chunking: TranscriptionChunking,
+    ) -> Result<CreateTranscriptionResponse> {
+        let chunks = chunking.split(req).await?;
+        let runs = chunking.runs(chunks).into_iter().map(|run| async move {
+            let mut parts = vec![];
+            let mut tail: Option<String> = None;
+            for mut chunk in run {
+                if let Some(tail) = tail.take() {
+                    chunk.req.prompt = Some(tail);
+                }
+                let res = self.create_transcription(chunk.req).await?;
+                tail = chunking.prompt_tail(&res.text);
+                parts.push((chunk.offset, res));
+            }
+            Ok::<_, LlmError>(parts)
+        });
+        let parts = futures_util::future::try_join_all(runs).await?;
+        Ok(CreateTranscriptionResponse::stitch(parts.into_iter().flatten().collect()))
+    }
+    
+    pub async fn list_models(&self) -> Result<ListModelsResponse> {
+        let req = self.prepare_request(ListModelsRequest);
+        let res = self.send(req).await?;
+        json::<ListModelsResponse>(res).await
+    }
+    
+    pub async fn retrieve_model(&self, id: impl Into<String>) -> Result<Model> {
+        let req = self.prepare_request(RetrieveModelRequest::new(id));
+        let res = self.send(req).await?;
+        json::<Model>(res).await
+    }
+    
+    /// Deletes a fine-tuned model.
+    pub async fn delete_model(&self, id: impl Into<String>) -> Result<DeleteModelResponse> {
+        let req = self.prepare_request(DeleteModelRequest::new(id));
+        let res = self.send(req).await?;
+        json::<DeleteModelResponse>(res).await
+    }
+    
+    /// Uploads a file. Paths and readers are streamed while the request is sent, so no total
+    /// timeout is applied and the upload is not retried.
+    pub async fn create_file(&self, req: CreateFileRequest) -> Result<FileObject> {
+        let req = self.prepare_stream_request(req.into_multipart().await?);
+        let res = self.send(req).await?;
+        json::<FileObject>(res).await
+    }
+    
+    /// Lists one page of files. See `list_all_files` to fetch every page.
+    pub async fn list_files(&self, req: ListFilesRequest) -> Result<ListFilesResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ListFilesResponse>(res).await
+    }
+    
+    /// Follows the pagination cursor until every file matching the request is fetched.
+    pub async fn list_all_files(&self, req: ListFilesRequest) -> Result<Vec<FileObject>> {
+        let mut files = vec![];
+        let mut next = Some(req);
+        while let Some(req) = next {
+            let page = self.list_files(req.clone()).await?;
+            next = page.next_page(&req);
+            files.extend(page.data);
+        }
+        Ok(files)
+    }
+    
+    pub async fn retrieve_file(&self, id: impl Into<String>) -> Result<FileObject> {
+        let req = self.prepare_request(RetrieveFileRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<FileObject>(res).await
+    }
+    
+    pub async fn delete_file(&self, id: impl Into<String>) -> Result<DeleteFileResponse> {
+        let req = self.prepare_request(DeleteFileRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<DeleteFileResponse>(res).await
+    }
+    
+    /// Downloads the content of a file as it arrives.
+    pub async fn file_content(&self, id: impl Into<String>) -> Result<ByteStream> {
+        let req = self.prepare_stream_request(FileContentRequest { id: id.into() });
+        let res = self.send(req).await?;
+        Ok(ByteStream::new(res))
+    }
+    
+    pub async fn create_fine_tuning_job(&self, req: CreateFineTuningJobRequest) -> Result<FineTuningJob> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<FineTuningJob>(res).await
+    }
+    
+    pub async fn list_fine_tuning_jobs(&self, req: ListFineTuningJobsRequest) -> Result<ListFineTuningJobsResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ListFineTuningJobsResponse>(res).await
+    }
+    
+    pub async fn retrieve_fine_tuning_job(&self, id: impl Into<String>) -> Result<FineTuningJob> {
+        let req = self.prepare_request(RetrieveFineTuningJobRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<FineTuningJob>(res).await
+    }
+    
+    pub async fn cancel_fine_tuning_job(&self, id: impl Into<String>) -> Result<FineTuningJob> {
+        let req = self.prepare_request(CancelFineTuningJobRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<FineTuningJob>(res).await
+    }
+    
+    pub async fn list_fine_tuning_events(&self, req: ListFineTuningEventsRequest) -> Result<ListFineTuningEventsResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ListFineTuningEventsResponse>(res).await
+    }
+    
+    pub async fn list_fine_tuning_checkpoints(
+        &self,
+        req: ListFineTuningCheckpointsRequest,
+    ) -> Result<ListFineTuningCheckpointsResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ListFineTuningCheckpointsResponse>(res).await
+    }
+    
+    pub async fn create_batch(&self, req: CreateBatchRequest) -> Result<Batch> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<Batch>(res).await
+    }
+    
+    pub async fn retrieve_batch(&self, id: impl Into<String>) -> Result<Batch> {
+        let req = self.prepare_request(RetrieveBatchRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<Batch>(res).await
+    }
+    
+    pub async fn cancel_batch(&self, id: impl Into<String>) -> Result<Batch> {
+        let req = self.prepare_request(CancelBatchRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<Batch>(res).await
+    }
+    
+    pub async fn list_batches(&self, req: ListBatchesRequest) -> Result<ListBatchesResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ListBatchesResponse>(res).await
+    }
+    
+    /// Runs the requests through the Batch API: uploads them as a JSONL file, creates the batch,
+    /// polls it until it ends, and joins the output and error files back to the requests. If the
+    /// batch failed validation or expired, the requests that never ran get its error.
+    /// Batches take up to 24 hours, so this can wait for a long time.
+    pub async fn run_batch<R: BatchRequest + Clone>(&self, input: BatchInput<R>, poll: PollOptions) -> Result<BatchRun<R>> {
+        let (endpoint, jsonl) = input.to_jsonl()?;
+        let file = self
+            .create_file(CreateFileRequest::new(FileSource::bytes("batch.jsonl", jsonl), FilePurpose::Batch))
+            .await?;
+        let mut batch = self.create_batch(CreateBatchRequest::new(file.id, endpoint)).await?;
+        let mut interval = poll.initial_interval;
+        while !batch.status.is_terminal() {
+            tokio::time::sleep(interval).await;
+            let status = batch.status;
+            batch = self.retrieve_batch(&batch.id).await?;
+            interval = poll.next_interval(interval, batch.status != status);
+        }
+        let output = match &batch.output_file_id {
+            Some(id) => self.file_content(id).await?.bytes().await?,
+            None => Default::default(),
+        };
+        let errors = match &batch.error_file_id {
+            Some(id) => self.file_content(id).await?.bytes().await?,
+            None => Default::default(),
+        };
+        let results = input.join(&output, &errors)?;
+        Ok(BatchRun::new(batch, results))
+    }
+    
+    pub async fn create_assistant(&self, req: CreateAssistantRequest) -> Result<Assistant> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<Assistant>(res).await
+    }
+    
+    pub async fn modify_assistant(&self, req: ModifyAssistantRequest) -> Result<Assistant> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<Assistant>(res).await
+    }
+    
+    pub async fn retrieve_assistant(&self, id: impl Into<String>) -> Result<Assistant> {
+        let req = self.prepare_request(RetrieveAssistantRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<Assistant>(res).await
+    }
+    
+    pub async fn delete_assistant(&self, id: impl Into<String>) -> Result<DeletionStatus> {
+        let req = self.prepare_request(DeleteAssistantRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<DeletionStatus>(res).await
+    }
+    
+    pub async fn list_assistants(&self, req: ListAssistantsRequest) -> Result<ListAssistantsResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ListAssistantsResponse>(res).await
+    }
+    
+    pub async fn create_thread(&self, req: CreateThreadRequest) -> Result<Thread> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<Thread>(res).await
+    }
+    
+    pub async fn modify_thread(&self, req: ModifyThreadRequest) -> Result<Thread> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<Thread>(res).await
+    }
+    
+    pub async fn retrieve_thread(&self, id: impl Into<String>) -> Result<Thread> {
+        let req = self.prepare_request(RetrieveThreadRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<Thread>(res).await
+    }
+    
+    pub async fn delete_thread(&self, id: impl Into<String>) -> Result<DeletionStatus> {
+        let req = self.prepare_request(DeleteThreadRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<DeletionStatus>(res).await
+    }
+    
+    pub async fn create_message(&self, req: CreateMessageRequest) -> Result<ThreadMessage> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ThreadMessage>(res).await
+    }
+    
+    pub async fn list_messages(&self, req: ListMessagesRequest) -> Result<ListMessagesResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ListMessagesResponse>(res).await
+    }
+    
+    pub async fn retrieve_message(&self, thread_id: impl Into<String>, id: impl Into<String>) -> Result<ThreadMessage> {
+        let req = self.prepare_request(RetrieveMessageRequest { thread_id: thread_id.into(), id: id.into() });
+        let res = self.send(req).await?;
+        json::<ThreadMessage>(res).await
+    }
+    
+    pub async fn modify_message(&self, req: ModifyMessageRequest) -> Result<ThreadMessage> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ThreadMessage>(res).await
+    }
+    
+    pub async fn delete_message(&self, thread_id: impl Into<String>, id: impl Into<String>) -> Result<DeletionStatus> {
+        let req = self.prepare_request(DeleteMessageRequest { thread_id: thread_id.into(), id: id.into() });
+        let res = self.send(req).await?;
+        json::<DeletionStatus>(res).await
+    }
+    
+    /// Starts a run. See `Run::wait` to poll it until it needs tool outputs or ends.
+    pub async fn create_run(&self, req: CreateRunRequest) -> Result<Run> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<Run>(res).await
+    }
+    
+    pub async fn retrieve_run(&self, thread_id: impl Into<String>, id: impl Into<String>) -> Result<Run> {
+        let req = self.prepare_request(RetrieveRunRequest { thread_id: thread_id.into(), id: id.into() });
+        let res = self.send(req).await?;
+        json::<Run>(res).await
+    }
+    
+    pub async fn cancel_run(&self, thread_id: impl Into<String>, id: impl Into<String>) -> Result<Run> {
+        let req = self.prepare_request(CancelRunRequest { thread_id: thread_id.into(), id: id.into() });
+        let res = self.send(req).await?;
+        json::<Run>(res).await
+    }
+    
+    /// Sends the outputs of the tool calls a run requires, and resumes it.
+    pub async fn submit_tool_outputs(&self, req: SubmitToolOutputsRequest) -> Result<Run> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<Run>(res).await
+    }
+    
+    pub async fn create_response(&self, req: CreateResponseRequest) -> Result<Response> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<Response>(res).await
+    }
+    
+    /// Creates a response and streams its semantic events. `Response::from_stream` folds them
+    /// back into the final response.
+    pub async fn create_response_stream(&self, mut req: CreateResponseRequest) -> Result<ResponseStream> {
+        req.stream = Some(true);
+        let req = self.prepare_stream_request(req);
+        let res = self.send(req).await?;
+        Ok(Box::pin(sse::sse_stream(res)))
+    }
+    
+    pub async fn retrieve_response(&self, id: impl Into<String>) -> Result<Response> {
+        let req = self.prepare_request(RetrieveResponseRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<Response>(res).await
+    }
+    
+    pub async fn cancel_response(&self, id: impl Into<String>) -> Result<Response> {
+        let req = self.prepare_request(CancelResponseRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<Response>(res).await
+    }
+    
+    pub async fn delete_response(&self, id: impl Into<String>) -> Result<DeleteResponseResponse> {
+        let req = self.prepare_request(DeleteResponseRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<DeleteResponseResponse>(res).await
+    }
+    
+    pub async fn create_vector_store(&self, req: CreateVectorStoreRequest) -> Result<VectorStore> {
+        req.validate()?;
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<VectorStore>(res).await
+    }
+    
+    pub async fn modify_vector_store(&self, req: ModifyVectorStoreRequest) -> Result<VectorStore> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<VectorStore>(res).await
+    }
+    
+    pub async fn retrieve_vector_store(&self, id: impl Into<String>) -> Result<VectorStore> {
+        let req = self.prepare_request(RetrieveVectorStoreRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<VectorStore>(res).await
+    }
+    
+    pub async fn delete_vector_store(&self, id: impl Into<String>) -> Result<DeleteVectorStoreResponse> {
+        let req = self.prepare_request(DeleteVectorStoreRequest { id: id.into() });
+        let res = self.send(req).await?;
+        json::<DeleteVectorStoreResponse>(res).await
+    }
+    
+    pub async fn list_vector_stores(&self, req: ListVectorStoresRequest) -> Result<ListVectorStoresResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ListVectorStoresResponse>(res).await
+    }
+    
+    /// Attaches a file to a vector store. See `VectorStoreFile::wait` to poll it until it is indexed.
+    pub async fn create_vector_store_file(&self, req: CreateVectorStoreFileRequest) -> Result<VectorStoreFile> {
+        req.validate()?;
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<VectorStoreFile>(res).await
+    }
+    
+    pub async fn list_vector_store_files(&self, req: ListVectorStoreFilesRequest) -> Result<ListVectorStoreFilesResponse> {
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<ListVectorStoreFilesResponse>(res).await
+    }
+    
+    pub async fn retrieve_vector_store_file(&self, vector_store_id: impl Into<String>, file_id: impl Into<String>) -> Result<VectorStoreFile> {
+        let req = self.prepare_request(RetrieveVectorStoreFileRequest {
+            vector_store_id: vector_store_id.into(),
+            file_id: file_id.into(),
+        });
+        let res = self.send(req).await?;
+        json::<VectorStoreFile>(res).await
+    }
+    
+    /// Removes a file from a vector store. The file itself is kept; delete it with `delete_file`.
+    pub async fn delete_vector_store_file(&self, vector_store_id: impl Into<String>, file_id: impl Into<String>) -> Result<DeleteVectorStoreResponse> {
+        let req = self.prepare_request(DeleteVectorStoreFileRequest {
+            vector_store_id: vector_store_id.into(),
+            file_id: file_id.into(),
+        });
+        let res = self.send(req).await?;
+        json::<DeleteVectorStoreResponse>(res).await
+    }
+    
+    /// Attaches several files at once. See `VectorStoreFileBatch::wait` to poll the batch until it is processed.
+    pub async fn create_vector_store_file_batch(&self, req: CreateVectorStoreFileBatchRequest) -> Result<VectorStoreFileBatch> {
+        req.validate()?;
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<VectorStoreFileBatch>(res).await
+    }
+    
+    pub async fn retrieve_vector_store_file_batch(&self, vector_store_id: impl Into<String>, batch_id: impl Into<String>) -> Result<VectorStoreFileBatch> {
+        let req = self.prepare_request(RetrieveVectorStoreFileBatchRequest {
+            vector_store_id: vector_store_id.into(),
+            batch_id: batch_id.into(),
+        });
+        let res = self.send(req).await?;
+        json::<VectorStoreFileBatch>(res).await
+    }
+    
+    pub async fn cancel_vector_store_file_batch(&self, vector_store_id: impl Into<String>, batch_id: impl Into<String>) -> Result<VectorStoreFileBatch> {
+        let req = self.prepare_request(CancelVectorStoreFileBatchRequest {
+            vector_store_id: vector_store_id.into(),
+            batch_id: batch_id.into(),
+        });
+        let res = self.send(req).await?;
+        json::<VectorStoreFileBatch>(res).await
+    }
+    
+    pub async fn search_vector_store(&self, req: SearchVectorStoreRequest) -> Result<SearchVectorStoreResponse> {
+        req.validate()?;
+        let req = self.prepare_request(req);
+        let res = self.send(req).await?;
+        json::<SearchVectorStoreResponse>(res).await
+    }
+    
+    /// Opens a Realtime API session with the model over a WebSocket, using the SDK's token, base
+    /// URL and headers. The timeout applies to the opening handshake only; failed handshakes are
+    /// not retried.
+    ///
+    /// The WebSocket connects directly with the system's root certificates, not through the HTTP
+    /// client: an SDK built with a `proxy` or a `client` is rejected with `LlmError::Validation`,
+    /// and proxy environment variables are not honoured.
+    pub async fn realtime(&self, model: impl Into<String>) -> Result<RealtimeSession> {
+        if self.custom_transport {
+            return Err(LlmError::Validation(
+                "realtime sessions cannot use a proxy or a user-supplied client".into(),
+            ));
+        }
+        let mut url = Url::parse(&self.ctx.url("realtime")).map_err(|e| LlmError::Validation(e.to_string()))?;
+        let scheme = match url.scheme() {
+            "https" => "wss",
+            "http" => "ws",
+            other => return Err(LlmError::Validation(format!("unsupported base url scheme {other}"))),
+        };
+        url.set_scheme(scheme).expect("ws and wss are valid schemes");
+        url.query_pairs_mut().append_pair("model", &model.into());
+        if let Some(version) = &self.ctx.api_version {
+            url.query_pairs_mut().append_pair("api-version", version);
+        }
+
+        let mut headers = self.ctx.headers.clone();
+        if !self.token.is_empty() {
+            let token = HeaderValue::from_str(&format!("Bearer {}", self.token))
+                .map_err(|e| LlmError::Validation(e.to_string()))?;
+            headers.insert(AUTHORIZATION, token);
+        }
+        if !headers.contains_key(OPENAI_BETA) {
+            headers.insert(OPENAI_BETA, HeaderValue::from_static(REALTIME_BETA));
+        }
+        tokio::time::timeout(self.timeout, RealtimeSession::connect(&url, &headers))
+            .await
+            .map_err(|_| LlmError::WebSocket("the handshake timed out".into()))?
     }
     
     fn prepare_request(&self, req: impl IntoRequest) -> RequestBuilder {
-        let req = req.into_request(self.client.clone());
-        let req = if self.token.is_empty() {
+        self.prepare_stream_request(req)
+            .timeout(self.timeout)
+    }
+    
+    /// Sends the request, retrying it according to the retry policy, and turns a non-success
+    /// status into `LlmError::Api`. A request whose body cannot be cloned is sent only once.
+    async fn send(&self, req: RequestBuilder) -> Result<reqwest::Response> {
+        let start = Instant::now();
+        let mut attempt = 1;
+        loop {
+            let Some(retry) = req.try_clone() else {
+                return check_status(req.send().await?).await;
+            };
+            let delay = match retry.send().await {
+                Ok(res) if RetryPolicy::is_retryable_status(res.status()) => {
+                    match self.retry_policy.delay(attempt, start.elapsed(), Some(res.headers())) {
+                        Some(delay) => delay,
+                        None => return check_status(res).await,
+                    }
+                }
+                Ok(res) => return check_status(res).await,
+                Err(e) if RetryPolicy::is_retryable_error(&e) => {
+                    match self.retry_policy.delay(attempt, start.elapsed(), None) {
+                        Some(delay) => delay,
+                        None => return Err(e.into()),
+                    }
+                }
+                Err(e) => return Err(e.into()),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+    
+    /// Streamed responses are read for as long as the model keeps generating, so no total timeout is applied.
+    fn prepare_stream_request(&self, req: impl IntoRequest) -> RequestBuilder {
+        let beta = req.beta().filter(|_| !self.ctx.headers.contains_key(OPENAI_BETA));
+        let mut req = req.into_request(&self.ctx);
+        if let Some(beta) = beta {
+            req = req.header(OPENAI_BETA, beta);
+        }
+        if self.token.is_empty() {
             req
         } else {
             req.bearer_auth(&self.token)
-        };
-        req.timeout(Duration::from_secs(TIMEOUT))
+        }
+    }
+}
+
+async fn check_status(res: reqwest::Response) -> Result<reqwest::Response> {
+    let status = res.status();
+    if status.is_success() {
+        Ok(res)
+    } else {
+        let body = res.text().await?;
+        Err(LlmError::from_response(status, body))
     }
-}
\ No newline at end of file
+}
+
+/// Percent-encodes an id for use as one segment of an endpoint path.
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    // URL parsing resolves `.`, `..` and even their `%2E` forms as dot-segments, so an id made of
+    // dots could point the request at another endpoint. Escaping the `%` keeps it a segment of its
+    // own, which the API answers with a not found error.
+    if !segment.is_empty() && segment.bytes().all(|byte| byte == b'.') {
+        return "%252E".repeat(segment.len());
+    }
+    let mut out = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' | b'@' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+/// Reads the whole body and deserializes it, keeping the raw body around on failure.
+async fn json<T: DeserializeOwned>(res: reqwest::Response) -> Result<T> {
+    let body = res.bytes().await?;
+    serde_json::from_slice(&body).map_err(|source| LlmError::Deserialize {
+        source,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{MockResponse, MockServer};
+
+    use super::*;
+    use anyhow::Result;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_retry_on_rate_limit() -> Result<()> {
+        let server = MockServer::start(vec![
+            MockResponse::new(429, "{}").header("retry-after-ms", "10"),
+            MockResponse::new(503, "{}").header("retry-after", "0"),
+            MockResponse::json(json!({ "ok": true })),
+        ])
+        .await;
+        let sdk = LLMSDK::new("token".into());
+        let req = sdk.ctx.client.post(format!("{}/v1/retry", server.url)).json(&json!({ "a": 1 }));
+        let res = sdk.send(req).await?;
+        assert_eq!(res.status(), 200);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests.iter().all(|req| req.method == "POST" && req.path == "/v1/retry"));
+        assert_eq!(requests[2].json(), json!({ "a": 1 }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::new(
+            429,
+            r#"{"error": {"message": "Rate limit reached", "type": "requests", "code": "rate_limit_exceeded"}}"#,
+        )
+        .header("retry-after", "0")])
+        .await;
+        let sdk = LLMSDK::new("token".into())
+            .with_retry_policy(RetryPolicy::default().with_max_attempts(2));
+        let err = sdk.send(sdk.ctx.client.get(&server.url)).await.unwrap_err();
+        assert!(err.is_rate_limited());
+        assert_eq!(server.requests().len(), 2);
+        Ok(())
+    }
+}
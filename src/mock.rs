@@ -0,0 +1,250 @@
+//! Minimal servers for tests. The HTTP/1.1 one answers every connection with the next canned
+//! response (repeating the last one) and records the requests it receives; the WebSocket one
+//! replays recorded Realtime events.
+
+use std::sync::{Arc, Mutex};
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use tokio_tungstenite::tungstenite::{
+    handshake::server::{Request, Response},
+    Message,
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        MockResponse {
+            status,
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    pub fn json(body: serde_json::Value) -> Self {
+        Self::new(200, body.to_string()).header("content-type", "application/json")
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+}
+
+pub(crate) struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    pub async fn start(responses: Vec<MockResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            let mut next = 0;
+            while let Ok((stream, _)) = listener.accept().await {
+                let res = responses[next.min(responses.len() - 1)].clone();
+                next += 1;
+                let recorded = recorded.clone();
+                tokio::spawn(serve(stream, res, recorded));
+            }
+        });
+        MockServer { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The method and path of every request, e.g. `GET /models`, in the order they came in.
+    pub fn calls(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().map(|req| format!("{} {}", req.method, req.path)).collect()
+    }
+}
+
+async fn serve(mut stream: TcpStream, res: MockResponse, recorded: Arc<Mutex<Vec<RecordedRequest>>>) -> Option<()> {
+    let mut buf = vec![];
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        let mut chunk = [0u8; 4096];
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(n, v)| (n.trim().to_string(), v.trim().to_string()))
+        .collect();
+    let mut req = RecordedRequest {
+        method,
+        path,
+        headers,
+        body: buf[header_end..].to_vec(),
+    };
+    if req.header("transfer-encoding") == Some("chunked") {
+        req.body = read_chunked(&mut stream, req.body).await?;
+    } else {
+        let len: usize = req.header("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+        while req.body.len() < len {
+            let mut chunk = [0u8; 4096];
+            let n = stream.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                break;
+            }
+            req.body.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    recorded.lock().unwrap().push(req);
+
+    let mut out = format!("HTTP/1.1 {} Mock\r\n", res.status);
+    for (name, value) in &res.headers {
+        out.push_str(&format!("{name}: {value}\r\n"));
+    }
+    out.push_str(&format!("content-length: {}\r\nconnection: close\r\n\r\n", res.body.len()));
+    let mut out = out.into_bytes();
+    out.extend_from_slice(&res.body);
+    stream.write_all(&out).await.ok()?;
+    stream.shutdown().await.ok()
+}
+
+async fn read_chunked(stream: &mut TcpStream, mut raw: Vec<u8>) -> Option<Vec<u8>> {
+    while !raw.windows(5).any(|w| w == b"0\r\n\r\n") {
+        let mut chunk = [0u8; 4096];
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        raw.extend_from_slice(&chunk[..n]);
+    }
+    let mut body = vec![];
+    let mut rest = &raw[..];
+    loop {
+        let line_end = rest.windows(2).position(|w| w == b"\r\n")?;
+        let size = usize::from_str_radix(std::str::from_utf8(&rest[..line_end]).ok()?.trim(), 16).ok()?;
+        rest = &rest[line_end + 2..];
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(&rest[..size]);
+        rest = &rest[size + 2..];
+    }
+}
+
+/// A WebSocket stand-in that accepts one connection and replays recorded server messages: the
+/// first group right after the handshake, then one more group each time the client sends a text
+/// message. It records the handshake head and every client message until the connection closes.
+pub(crate) struct MockWebSocketServer {
+    pub url: String,
+    head: Arc<Mutex<String>>,
+    received: Arc<Mutex<Vec<Message>>>,
+    task: tokio::task::JoinHandle<Option<()>>,
+}
+
+impl MockWebSocketServer {
+    pub async fn start(replay: Vec<Vec<Message>>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let head = Arc::new(Mutex::new(String::new()));
+        let received = Arc::new(Mutex::new(vec![]));
+        let (recorded_head, recorded) = (head.clone(), received.clone());
+        let task = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.ok()?;
+            serve_websocket(stream, replay, recorded_head, recorded).await
+        });
+        MockWebSocketServer {
+            url,
+            head,
+            received,
+            task,
+        }
+    }
+
+    /// Waits until the connection is closed.
+    pub async fn finished(&mut self) {
+        let _ = (&mut self.task).await;
+    }
+
+    pub fn head(&self) -> String {
+        self.head.lock().unwrap().clone()
+    }
+
+    pub fn received(&self) -> Vec<Message> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+async fn serve_websocket(
+    stream: TcpStream,
+    replay: Vec<Vec<Message>>,
+    recorded_head: Arc<Mutex<String>>,
+    recorded: Arc<Mutex<Vec<Message>>>,
+) -> Option<()> {
+    // the callback's signature, and its large error type, are tungstenite's
+    #[allow(clippy::result_large_err)]
+    let record_head = |req: &Request, res: Response| {
+        let mut head = format!("{} {} {:?}\r\n", req.method(), req.uri(), req.version());
+        for (name, value) in req.headers() {
+            head.push_str(&format!("{name}: {}\r\n", value.to_str().unwrap_or_default()));
+        }
+        *recorded_head.lock().unwrap() = head;
+        Ok(res)
+    };
+    let mut socket = tokio_tungstenite::accept_hdr_async(stream, record_head).await.ok()?;
+    let mut replay = replay.into_iter();
+    loop {
+        for message in replay.next().unwrap_or_default() {
+            socket.send(message).await.ok()?;
+        }
+        // wait for the next text message; tungstenite answers pings and closes
+        loop {
+            let message = socket.next().await?.ok()?;
+            recorded.lock().unwrap().push(message.clone());
+            if let Message::Text(_) = message {
+                break;
+            }
+        }
+    }
+}
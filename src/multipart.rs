@@ -0,0 +1,281 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use bytes::{Bytes, BytesMut};
+use futures_util::{stream, Stream};
+use reqwest::{
+    multipart::{Form, Part},
+    Body, RequestBuilder,
+};
+use serde::Serialize;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{IntoRequest, RequestContext, Result};
+
+/// A file to upload: a path on disk, a buffer in memory, or any async reader.
+pub enum FileSource {
+    Path(PathBuf),
+    Bytes {
+        filename: String,
+        data: Bytes,
+    },
+    Reader {
+        filename: String,
+        reader: Box<dyn AsyncRead + Send + Sync + Unpin>,
+    },
+}
+
+impl FileSource {
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        FileSource::Path(path.into())
+    }
+
+    /// The filename tells the API the file format, e.g. `image.png`.
+    pub fn bytes(filename: impl Into<String>, data: impl Into<Bytes>) -> Self {
+        FileSource::Bytes {
+            filename: filename.into(),
+            data: data.into(),
+        }
+    }
+
+    /// The filename tells the API the file format, e.g. `image.png`.
+    pub fn reader(filename: impl Into<String>, reader: impl AsyncRead + Send + Sync + Unpin + 'static) -> Self {
+        FileSource::Reader {
+            filename: filename.into(),
+            reader: Box::new(reader),
+        }
+    }
+
+    pub fn filename(&self) -> String {
+        match self {
+            FileSource::Path(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "file".into()),
+            FileSource::Bytes { filename, .. } | FileSource::Reader { filename, .. } => filename.clone(),
+        }
+    }
+
+    /// Reads the whole file into memory.
+    pub(crate) async fn load(self) -> Result<(String, Bytes)> {
+        let filename = self.filename();
+        let data = match self {
+            FileSource::Path(path) => tokio::fs::read(path).await?.into(),
+            FileSource::Bytes { data, .. } => data,
+            FileSource::Reader { mut reader, .. } => {
+                let mut data = vec![];
+                reader.read_to_end(&mut data).await?;
+                data.into()
+            }
+        };
+        Ok((filename, data))
+    }
+}
+
+impl fmt::Debug for FileSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileSource::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            FileSource::Bytes { filename, data } => f
+                .debug_struct("Bytes")
+                .field("filename", filename)
+                .field("len", &data.len())
+                .finish(),
+            FileSource::Reader { filename, .. } => {
+                f.debug_struct("Reader").field("filename", filename).finish_non_exhaustive()
+            }
+        }
+    }
+}
+
+impl From<&str> for FileSource {
+    fn from(path: &str) -> Self {
+        FileSource::Path(path.into())
+    }
+}
+
+impl From<String> for FileSource {
+    fn from(path: String) -> Self {
+        FileSource::Path(path.into())
+    }
+}
+
+impl From<&Path> for FileSource {
+    fn from(path: &Path) -> Self {
+        FileSource::Path(path.into())
+    }
+}
+
+impl From<PathBuf> for FileSource {
+    fn from(path: PathBuf) -> Self {
+        FileSource::Path(path)
+    }
+}
+
+/// A `multipart/form-data` body, encoded by `reqwest::multipart::Form`. Parts are buffered,
+/// except files added with `file_stream`, which are read while the body is sent.
+#[derive(Debug)]
+pub(crate) struct MultipartForm {
+    form: Form,
+}
+
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+impl MultipartForm {
+    pub fn new() -> Self {
+        MultipartForm { form: Form::new() }
+    }
+
+    pub fn text(mut self, name: &str, value: impl fmt::Display) -> Self {
+        self.form = self.form.text(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Adds a text part for every value that is set, formatted as its json string.
+    pub fn optional<T: Serialize>(self, name: &str, value: Option<T>) -> Self {
+        match value.map(|v| serde_json::to_value(v)) {
+            Some(Ok(serde_json::Value::String(s))) => self.text(name, s),
+            Some(Ok(value)) => self.text(name, value),
+            _ => self,
+        }
+    }
+
+    pub fn file(self, name: &str, filename: &str, data: impl Into<Bytes>) -> Self {
+        self.part(name, filename, Part::stream(Body::from(data.into())))
+    }
+
+    /// Loads the file and adds it as a part.
+    pub async fn file_source(self, name: &str, file: FileSource) -> Result<Self> {
+        let (filename, data) = file.load().await?;
+        Ok(self.file(name, &filename, data))
+    }
+
+    /// Adds a file part that is read while the request is sent instead of being loaded into
+    /// memory. Only the file is opened here, so a missing file fails before anything is sent.
+    pub async fn file_stream(self, name: &str, file: FileSource) -> Result<Self> {
+        let filename = file.filename();
+        let part = match file {
+            FileSource::Path(path) => {
+                let file = tokio::fs::File::open(path).await?;
+                let len = file.metadata().await?.len();
+                Part::stream_with_length(Body::wrap_stream(read_chunks(Box::new(file))), len)
+            }
+            FileSource::Bytes { data, .. } => return Ok(self.file(name, &filename, data)),
+            FileSource::Reader { reader, .. } => Part::stream(Body::wrap_stream(read_chunks(reader))),
+        };
+        Ok(self.part(name, &filename, part))
+    }
+
+    pub fn into_form(self) -> Form {
+        self.form
+    }
+
+    fn part(mut self, name: &str, filename: &str, part: Part) -> Self {
+        let part = part
+            .file_name(filename.to_string())
+            .mime_str(mime_type(filename))
+            .expect("the guessed content types are valid");
+        self.form = self.form.part(name.to_string(), part);
+        self
+    }
+}
+
+/// Reads the upload in chunks as the body is sent.
+fn read_chunks(
+    reader: Box<dyn AsyncRead + Send + Sync + Unpin>,
+) -> impl Stream<Item = std::io::Result<Bytes>> + Send + Sync + 'static {
+    stream::unfold(Some(reader), |reader| async move {
+        let mut reader = reader?;
+        let mut buf = BytesMut::with_capacity(UPLOAD_CHUNK_SIZE);
+        match reader.read_buf(&mut buf).await {
+            Ok(0) => None,
+            Ok(_) => Some((Ok(buf.freeze()), Some(reader))),
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
+
+/// Guesses the content type of an upload from its extension.
+pub(crate) fn mime_type(filename: &str) -> &'static str {
+    let ext = Path::new(filename)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "gif" => "image/gif",
+        "mp3" | "mpga" | "mpeg" => "audio/mpeg",
+        "mp4" | "m4a" => "audio/mp4",
+        "wav" => "audio/wav",
+        "webm" => "audio/webm",
+        "ogg" | "oga" => "audio/ogg",
+        "flac" => "audio/flac",
+        "json" => "application/json",
+        "jsonl" => "application/jsonl",
+        "txt" => "text/plain",
+        "pdf" => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// A request with a `multipart/form-data` body, built by the SDK from upload request types
+/// once their files are loaded or opened. The body is a stream, so it is never retried.
+#[derive(Debug)]
+pub struct MultipartRequest {
+    path: String,
+    form: MultipartForm,
+}
+
+impl MultipartRequest {
+    pub(crate) fn new(path: impl Into<String>, form: MultipartForm) -> Self {
+        MultipartRequest {
+            path: path.into(),
+            form,
+        }
+    }
+}
+
+impl IntoRequest for MultipartRequest {
+    fn into_request(self, ctx: &RequestContext) -> RequestBuilder {
+        ctx.post(&self.path).multipart(self.form.into_form())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{MockResponse, MockServer};
+
+    use super::*;
+    use anyhow::Result;
+
+    #[tokio::test]
+    async fn test_multipart_form_encode() -> Result<()> {
+        let server = MockServer::start(vec![MockResponse::new(200, "")]).await;
+        let form = MultipartForm::new()
+            .text("prompt", "a \"cute\" cat")
+            .optional("n", Some(2))
+            .optional::<String>("user", None)
+            .file_source("image", FileSource::reader("cat.png", &b"PNG"[..]))
+            .await?;
+        reqwest::Client::new().post(&server.url).multipart(form.into_form()).send().await?;
+
+        let req = &server.requests()[0];
+        let content_type = req.header("content-type").unwrap();
+        let boundary = content_type.strip_prefix("multipart/form-data; boundary=").unwrap();
+        assert_eq!(req.header("content-length"), Some(req.body.len().to_string().as_str()));
+        assert_eq!(
+            String::from_utf8_lossy(&req.body),
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"prompt\"\r\n\r\na \"cute\" cat\r\n\
+                 --{boundary}\r\nContent-Disposition: form-data; name=\"n\"\r\n\r\n2\r\n\
+                 --{boundary}\r\nContent-Disposition: form-data; name=\"image\"; filename=\"cat.png\"\r\nContent-Type: image/png\r\n\r\nPNG\r\n\
+                 --{boundary}--\r\n"
+            )
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+/// How often a long-running job is polled. The interval starts at `initial_interval` and grows
+/// by half whenever a poll brings no news, up to `max_interval`; any change resets it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollOptions {
+    pub initial_interval: Duration,
+    /// Raised to `initial_interval` when it is smaller.
+    pub max_interval: Duration,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        PollOptions {
+            initial_interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+impl PollOptions {
+    pub fn with_interval(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_interval = initial;
+        self.max_interval = max;
+        self
+    }
+
+    /// The wait before the next poll, given the last one and whether the poll brought news.
+    pub(crate) fn next_interval(&self, current: Duration, changed: bool) -> Duration {
+        if changed {
+            self.initial_interval
+        } else {
+            let max = self.max_interval.max(self.initial_interval);
+            current.saturating_add(current / 2).clamp(self.initial_interval, max)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_interval_backoff() {
+        let poll = PollOptions::default().with_interval(Duration::from_secs(2), Duration::from_secs(5));
+        let mut interval = poll.initial_interval;
+        let mut intervals = vec![];
+        for _ in 0..4 {
+            interval = poll.next_interval(interval, false);
+            intervals.push(interval.as_secs_f64());
+        }
+        assert_eq!(intervals, [3.0, 4.5, 5.0, 5.0]);
+        assert_eq!(poll.next_interval(interval, true), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_poll_interval_inverted_bounds() {
+        let poll = PollOptions::default().with_interval(Duration::from_secs(10), Duration::from_secs(1));
+        assert_eq!(poll.next_interval(poll.initial_interval, false), Duration::from_secs(10));
+        assert_eq!(poll.next_interval(Duration::ZERO, false), Duration::from_secs(10));
+        assert_eq!(poll.next_interval(Duration::MAX, false), Duration::from_secs(10));
+        assert_eq!(poll.next_interval(Duration::from_secs(3), true), Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{header::HeaderMap, StatusCode};
+
+/// When and how often a failed request is sent again. Requests are retried on connection
+/// errors, `429 Too Many Requests` and 5xx responses, waiting for the delay the server asks
+/// for in `Retry-After`/`x-ratelimit-reset-*` or else a jittered exponential backoff. A server
+/// delay is never shortened; when it ends after the deadline the request fails right away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Backoff before the first retry, doubled for every following one.
+    pub initial_backoff: Duration,
+    /// Upper bound of the computed backoff.
+    pub max_backoff: Duration,
+    /// Give up once the next attempt would start after this much time since the first one.
+    pub deadline: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            deadline: Some(Duration::from_secs(120)),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Sends every request exactly once.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Option<Duration>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+
+    pub(crate) fn is_retryable_error(e: &reqwest::Error) -> bool {
+        e.is_connect()
+    }
+
+    /// The delay before attempt `attempt + 1`, `None` when no attempt is left or the delay would
+    /// end after the deadline.
+    pub(crate) fn delay(&self, attempt: u32, elapsed: Duration, headers: Option<&HeaderMap>) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let delay = headers
+            .and_then(server_delay)
+            .unwrap_or_else(|| self.backoff(attempt));
+        let end = elapsed.checked_add(delay)?;
+        match self.deadline {
+            Some(deadline) if end > deadline => None,
+            _ => Some(delay),
+        }
+    }
+
+    /// Exponential backoff with jitter: a random delay between half and all of `initial * 2^(attempt - 1)`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff);
+        exp.mul_f64(0.5 + random() * 0.5)
+    }
+}
+
+/// The delay the server asked for, if any. `retry-after-ms` and `retry-after` are specific to
+/// the failed request and win; otherwise the later of the `x-ratelimit-reset-*` headers is used.
+fn server_delay(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name)?.to_str().ok().map(str::trim);
+    let seconds = |secs: f64| Duration::try_from_secs_f64(secs.max(0.0)).ok();
+    header("retry-after-ms")
+        .and_then(|v| v.parse::<f64>().ok())
+        .and_then(|ms| seconds(ms / 1000.0))
+        .or_else(|| parse_retry_after(header("retry-after")?))
+        .or_else(|| {
+            ["x-ratelimit-reset-requests", "x-ratelimit-reset-tokens"]
+                .into_iter()
+                .filter_map(|name| parse_reset(header(name)?))
+                .max()
+        })
+}
+
+/// `Retry-After` is either a number of seconds or an HTTP date, e.g. `Wed, 21 Oct 2015 07:28:00 GMT`.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    match value.parse::<f64>() {
+        Ok(secs) => Duration::try_from_secs_f64(secs.max(0.0)).ok(),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+        }
+    }
+}
+
+/// Parses the duration format of the `x-ratelimit-reset-*` headers, e.g. `1s`, `6m0s` or `20ms`.
+fn parse_reset(value: &str) -> Option<Duration> {
+    let mut rest = value.trim();
+    let mut total = 0.0;
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let split = rest.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        let (number, tail) = rest.split_at(split);
+        let number: f64 = number.parse().ok()?;
+        let unit_len = tail.find(|c: char| c.is_ascii_digit()).unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        total += number
+            * match unit {
+                "h" => 3600.0,
+                "m" => 60.0,
+                "s" => 1.0,
+                "ms" => 0.001,
+                _ => return None,
+            };
+        rest = tail;
+    }
+    Duration::try_from_secs_f64(total).ok()
+}
+
+/// A random number in [0, 1), good enough for jitter.
+fn random() -> f64 {
+    (random_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Random bits from the randomly keyed std hasher, so no rng dependency is needed.
+pub(crate) fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    hasher.write_u32(nanos);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_ratelimit_reset() {
+        assert_eq!(parse_reset("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(parse_reset("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(parse_reset("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(parse_reset("1h2m3.5s"), Some(Duration::from_secs_f64(3723.5)));
+        assert_eq!(parse_reset("soon"), None);
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::default().with_backoff(Duration::from_secs(1), Duration::from_secs(3));
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("2s"));
+        assert_eq!(policy.delay(1, Duration::ZERO, Some(&headers)), Some(Duration::from_secs(2)));
+        // not capped by max_backoff, but ends after the deadline
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("6m0s"));
+        assert_eq!(policy.delay(1, Duration::ZERO, Some(&headers)), None);
+        let unbounded = policy.with_deadline(None);
+        assert_eq!(unbounded.delay(1, Duration::ZERO, Some(&headers)), Some(Duration::from_secs(360)));
+        headers.insert("retry-after", HeaderValue::from_static("2"));
+        // no attempts left
+        assert_eq!(policy.delay(3, Duration::ZERO, Some(&headers)), None);
+        // past the deadline
+        assert_eq!(policy.delay(1, Duration::from_secs(119), Some(&headers)), None);
+
+        let delay = policy.delay(2, Duration::ZERO, None).unwrap();
+        assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        // capped by max_backoff
+        let delay = policy.with_max_attempts(10).delay(5, Duration::ZERO, None).unwrap();
+        assert!(delay >= Duration::from_millis(1500) && delay <= Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_retry_delay_mixed_headers() {
+        let policy = RetryPolicy::default();
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after-ms", HeaderValue::from_static("20"));
+        headers.insert("retry-after", HeaderValue::from_static("1"));
+        headers.insert("x-ratelimit-reset-requests", HeaderValue::from_static("20ms"));
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("6m0s"));
+        assert_eq!(policy.delay(1, Duration::ZERO, Some(&headers)), Some(Duration::from_millis(20)));
+        headers.remove("retry-after-ms");
+        assert_eq!(policy.delay(1, Duration::ZERO, Some(&headers)), Some(Duration::from_secs(1)));
+        headers.insert("retry-after", HeaderValue::from_static("60"));
+        assert_eq!(policy.delay(1, Duration::ZERO, Some(&headers)), Some(Duration::from_secs(60)));
+        headers.remove("retry-after");
+        assert_eq!(policy.delay(1, Duration::ZERO, Some(&headers)), None);
+    }
+
+    #[test]
+    fn test_retry_after_http_date() {
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::ZERO));
+        let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(60));
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_str(&date).unwrap());
+        let delay = RetryPolicy::default().delay(1, Duration::ZERO, Some(&headers)).unwrap();
+        assert!(delay > Duration::from_secs(58) && delay <= Duration::from_secs(60), "{delay:?}");
+        assert_eq!(parse_retry_after("tomorrow"), None);
+    }
+
+    #[test]
+    fn test_retry_delay_unrepresentable_headers() {
+        let policy = RetryPolicy::default();
+        for value in ["inf", "1e20", "NaN"] {
+            let mut headers = HeaderMap::new();
+            headers.insert("retry-after", HeaderValue::from_static(value));
+            let delay = policy.delay(1, Duration::MAX, Some(&headers));
+            assert!(delay.is_none(), "{value}");
+            let delay = policy.delay(1, Duration::ZERO, Some(&headers)).unwrap();
+            assert!(delay <= policy.max_backoff, "{value}");
+        }
+        assert_eq!(parse_reset("99999999999999999999999h"), None);
+    }
+}
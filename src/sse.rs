@@ -0,0 +1,154 @@
+use std::collections::VecDeque;
+
+use futures_util::{stream, Stream};
+use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::{error::ApiErrorResponse, LlmError, Result};
+
+/// Incremental decoder for a `text/event-stream` body. Bytes can be fed in arbitrary
+/// pieces; only the `data` field of each event is kept, comments and other fields are dropped.
+#[derive(Debug, Default)]
+pub(crate) struct SseDecoder {
+    buf: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    /// Feeds the next piece of the body and returns the payload of every event it completes.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buf.extend_from_slice(chunk);
+        let mut events = vec![];
+        while let Some(pos) = self.buf.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=pos).collect();
+            if let Some(event) = self.process_line(&line) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Flushes the last event when the body ends without a trailing blank line.
+    pub fn finish(&mut self) -> Option<String> {
+        let line = std::mem::take(&mut self.buf);
+        self.process_line(&line);
+        self.dispatch()
+    }
+
+    fn process_line(&mut self, line: &[u8]) -> Option<String> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        // lines starting with a colon are comments, typically keep-alives
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        if field == "data" {
+            self.data.push(value.to_string());
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<String> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let data = self.data.join("\n");
+        self.data.clear();
+        Some(data)
+    }
+}
+
+struct SseState {
+    status: StatusCode,
+    res: Option<Response>,
+    decoder: SseDecoder,
+    pending: VecDeque<String>,
+}
+
+/// Errors can arrive mid-stream as an event carrying the usual `{"error": {...}}` object.
+fn parse_event<T: DeserializeOwned>(status: StatusCode, data: String) -> Result<T> {
+    serde_json::from_str::<T>(&data).map_err(|source| {
+        match serde_json::from_str::<ApiErrorResponse>(&data) {
+            Ok(res) => LlmError::Api {
+                status,
+                error: Some(res.error),
+                body: data,
+            },
+            Err(_) => LlmError::Deserialize { source, body: data },
+        }
+    })
+}
+
+/// Turns a streaming response into a stream of json events, ending at `data: [DONE]`.
+pub(crate) fn sse_stream<T>(res: Response) -> impl Stream<Item = Result<T>>
+where
+    T: DeserializeOwned,
+{
+    let state = SseState {
+        status: res.status(),
+        res: Some(res),
+        decoder: SseDecoder::default(),
+        pending: VecDeque::new(),
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(data) = state.pending.pop_front() {
+                if data == "[DONE]" {
+                    return None;
+                }
+                return Some((parse_event(state.status, data), state));
+            }
+            let res = state.res.as_mut()?;
+            match res.chunk().await {
+                Ok(Some(chunk)) => {
+                    let events = state.decoder.feed(&chunk);
+                    state.pending.extend(events);
+                }
+                Ok(None) => {
+                    state.res = None;
+                    state.pending.extend(state.decoder.finish());
+                }
+                Err(e) => {
+                    state.res = None;
+                    return Some((Err(e.into()), state));
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_decoder_split_events() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.feed(b": keep-alive\n\ndata: {\"a\"").is_empty());
+        assert_eq!(decoder.feed(b":1}\r\n\r\ndata: [DO"), vec!["{\"a\":1}"]);
+        assert_eq!(decoder.feed(b"NE]\n\n"), vec!["[DONE]"]);
+        assert_eq!(decoder.finish(), None);
+    }
+
+    #[test]
+    fn test_sse_error_event() {
+        let data = r#"{"error": {"message": "The server had an error", "type": "server_error"}}"#;
+        let err = parse_event::<Vec<u8>>(StatusCode::OK, data.into()).unwrap_err();
+        assert_eq!(err.api_error().unwrap().message, "The server had an error");
+    }
+
+    #[test]
+    fn test_sse_decoder_multiline_and_unterminated() {
+        let mut decoder = SseDecoder::default();
+        let events = decoder.feed(b"event: message\ndata: first\ndata: second\nid: 1\n\ndata:last");
+        assert_eq!(events, vec!["first\nsecond"]);
+        assert_eq!(decoder.finish(), Some("last".to_string()));
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+
+use crate::{LlmError, Result, Tool};
+
+/// A type that can describe itself as a JSON Schema, used to declare tool parameters.
+pub trait ToSchema {
+    /// Whether the model may leave out a field of this type.
+    const OPTIONAL: bool = false;
+
+    fn to_schema() -> Value;
+}
+
+macro_rules! impl_to_schema {
+    ($schema_type:literal => $($ty:ty),+) => {
+        $(
+            impl ToSchema for $ty {
+                fn to_schema() -> Value {
+                    json!({ "type": $schema_type })
+                }
+            }
+        )+
+    };
+}
+
+impl_to_schema!("string" => String, char);
+impl_to_schema!("integer" => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_to_schema!("number" => f32, f64);
+impl_to_schema!("boolean" => bool);
+
+impl<T: ToSchema> ToSchema for Option<T> {
+    const OPTIONAL: bool = true;
+
+    fn to_schema() -> Value {
+        T::to_schema()
+    }
+}
+
+impl<T: ToSchema> ToSchema for Vec<T> {
+    fn to_schema() -> Value {
+        json!({ "type": "array", "items": T::to_schema() })
+    }
+}
+
+impl<T: ToSchema> ToSchema for HashMap<String, T> {
+    fn to_schema() -> Value {
+        json!({ "type": "object", "additionalProperties": T::to_schema() })
+    }
+}
+
+impl ToSchema for Value {
+    fn to_schema() -> Value {
+        json!({})
+    }
+}
+
+/// Builds the JSON Schema of an object field by field.
+#[derive(Debug, Default)]
+pub struct ObjectSchema {
+    properties: Map<String, Value>,
+    required: Vec<String>,
+}
+
+impl ObjectSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field<T: ToSchema>(mut self, name: &str, description: &str) -> Self {
+        let mut schema = T::to_schema();
+        let description = description.trim();
+        if !description.is_empty() {
+            schema["description"] = description.into();
+        }
+        self.properties.insert(name.to_string(), schema);
+        if !T::OPTIONAL {
+            self.required.push(name.to_string());
+        }
+        self
+    }
+
+    pub fn build(self) -> Value {
+        json!({
+            "type": "object",
+            "properties": self.properties,
+            "required": self.required,
+        })
+    }
+}
+
+/// A function the model can call. Implemented by hand or with the [`tool_function!`](crate::tool_function) macro.
+pub trait ToolFunction: DeserializeOwned {
+    fn name() -> String;
+
+    fn description() -> Option<String> {
+        None
+    }
+
+    /// The JSON Schema of the function arguments.
+    fn parameters() -> Value;
+
+    fn tool() -> Tool {
+        Tool::function(Self::name(), Self::description(), Self::parameters())
+    }
+
+    fn parse_arguments(arguments: &str) -> Result<Self> {
+        serde_json::from_str(arguments).map_err(|source| LlmError::Deserialize {
+            source,
+            body: arguments.to_string(),
+        })
+    }
+}
+
+/// Declares a struct of tool parameters and implements [`ToolFunction`] for it. The struct name
+/// is the function name, doc comments become the descriptions, and `Option` fields are optional.
+///
+/// ```ignore
+/// llm_sdk::tool_function! {
+///     /// Get the current weather in a given location
+///     pub struct get_weather {
+///         /// The city and state, e.g. San Francisco, CA
+///         pub location: String,
+///         pub unit: Option<String>,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! tool_function {
+    (
+        $(#[doc = $doc:literal])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[doc = $field_doc:literal])*
+                $field_vis:vis $field:ident : $ty:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[doc = $doc])*
+        #[derive(Debug, Clone)]
+        #[allow(non_camel_case_types)]
+        $vis struct $name {
+            $(
+                $(#[doc = $field_doc])*
+                $field_vis $field: $ty,
+            )*
+        }
+
+        // `#[serde(crate)]` only takes a literal path, which can't name this crate when the
+        // dependency is renamed; derive on a copy of the struct that sees serde through a local alias
+        const _: () = {
+            use $crate::__private::serde as __serde;
+
+            #[derive(__serde::Deserialize)]
+            #[serde(crate = "__serde")]
+            struct Arguments {
+                $($field: $ty,)*
+            }
+
+            impl<'de> __serde::Deserialize<'de> for $name {
+                fn deserialize<D: __serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+                    let args = Arguments::deserialize(deserializer)?;
+                    ::std::result::Result::Ok($name { $($field: args.$field,)* })
+                }
+            }
+        };
+
+        impl $crate::ToolFunction for $name {
+            fn name() -> String {
+                stringify!($name).to_string()
+            }
+
+            fn description() -> Option<String> {
+                let description = concat!($($doc,)* "").trim();
+                (!description.is_empty()).then(|| description.to_string())
+            }
+
+            fn parameters() -> $crate::__private::serde_json::Value {
+                $crate::ObjectSchema::new()
+                    $(.field::<$ty>(stringify!($field), concat!($($field_doc,)* "")))*
+                    .build()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use crate::{ChatCompletionRequest, ChatCompletionModel, ChatCompletionMessage, ChatCompletionResponse, ToolChoice};
+
+    crate::tool_function! {
+        /// Get the current weather in a given location
+        struct get_weather {
+            /// The city and state, e.g. San Francisco, CA
+            location: String,
+            /// Celsius or fahrenheit
+            unit: Option<String>,
+            days: Vec<u32>,
+        }
+    }
+
+    #[test]
+    fn test_tool_function_schema() -> Result<()> {
+        let req = ChatCompletionRequest {
+            tools: vec![get_weather::tool()],
+            tool_choice: Some(ToolChoice::Function("get_weather".into())),
+            ..ChatCompletionRequest::new(ChatCompletionModel::Gpt4o, [ChatCompletionMessage::user("Weather in Paris?")])
+        };
+        let value = serde_json::to_value(req)?;
+        assert_eq!(value["tool_choice"], json!({ "type": "function", "function": { "name": "get_weather" } }));
+        assert_eq!(
+            value["tools"],
+            json!([{
+                "type": "function",
+                "function": {
+                    "name": "get_weather",
+                    "description": "Get the current weather in a given location",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "location": { "type": "string", "description": "The city and state, e.g. San Francisco, CA" },
+                            "unit": { "type": "string", "description": "Celsius or fahrenheit" },
+                            "days": { "type": "array", "items": { "type": "integer" } },
+                        },
+                        "required": ["location", "days"],
+                    },
+                },
+            }])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tool_call_round_trip() -> Result<()> {
+        let res: ChatCompletionResponse = serde_json::from_value(json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_abc",
+                        "type": "function",
+                        "function": { "name": "get_weather", "arguments": "{\"location\":\"Paris\",\"days\":[1]}" }
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            }]
+        }))?;
+        let message = res.choices[0].message.clone();
+        let call = &message.tool_calls[0];
+        assert!(call.function.is::<get_weather>());
+        let args = call.function.parse::<get_weather>()?;
+        assert_eq!(args.location, "Paris");
+        assert_eq!(args.unit, None);
+        assert_eq!(args.days, vec![1]);
+
+        let reply = ChatCompletionMessage::tool(&call.id, "sunny");
+        let history = serde_json::to_value([ChatCompletionMessage::from(message), reply])?;
+        assert_eq!(history[0]["tool_calls"][0]["id"], "call_abc");
+        assert_eq!(history[1], json!({ "role": "tool", "content": "sunny", "tool_call_id": "call_abc" }));
+        Ok(())
+    }
+}
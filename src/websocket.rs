@@ -0,0 +1,54 @@
+//! The WebSocket connection of the Realtime API. The protocol is tokio-tungstenite's; this only
+//! opens the connection with the SDK's headers and maps its errors.
+
+use reqwest::{header::HeaderMap, Url};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{tungstenite::client::IntoClientRequest, MaybeTlsStream, WebSocketStream};
+
+use crate::Result;
+
+pub(crate) type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Opens a connection to a `ws://` or `wss://` URL, sending the headers with the handshake.
+/// A refused upgrade is returned as an API error built from the HTTP response.
+pub(crate) async fn connect(url: &Url, headers: &HeaderMap) -> Result<WebSocket> {
+    let mut request = url.as_str().into_client_request()?;
+    request.headers_mut().extend(headers.clone());
+    // audio is sent in small chunks; don't hold them back
+    let (socket, _) = tokio_tungstenite::connect_async_with_config(request, None, true).await?;
+    Ok(socket)
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_connect_ipv6_host() -> Result<()> {
+        let Ok(listener) = tokio::net::TcpListener::bind("[::1]:0").await else {
+            // no IPv6 loopback in this environment
+            return Ok(());
+        };
+        let port = listener.local_addr()?.port();
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut socket = BufReader::new(socket);
+            let mut head = String::new();
+            while !head.ends_with("\r\n\r\n") {
+                socket.read_line(&mut head).await.unwrap();
+            }
+            socket.write_all(b"HTTP/1.1 403 Forbidden\r\ncontent-length: 2\r\n\r\nno").await.unwrap();
+            head
+        });
+        let url = Url::parse(&format!("ws://[::1]:{port}/realtime")).unwrap();
+        let Err(err) = connect(&url, &HeaderMap::new()).await else {
+            panic!("the handshake should be rejected");
+        };
+        assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+        assert!(server.await.unwrap().contains(&format!("Host: [::1]:{port}\r\n")));
+        Ok(())
+    }
+}